@@ -1,22 +1,72 @@
+use anchor_lang::prelude::Pubkey;
+
 pub const GLOBAL_STATE_SEED: &[u8] = b"GLOBAL_STATE_SEED";
 
 pub const USER_STATE_SEED: &[u8] = b"USER_STATE_SEED";
 
 pub const VAULT_SEED: &[u8] = b"VAULT_SEED";
 
+pub const FEE_SCHEDULE_SEED: &[u8] = b"FEE_SCHEDULE_SEED";
+
+pub const GIVEAWAY_SEED: &[u8] = b"GIVEAWAY_SEED";
+
+pub const GIVEAWAY_ROUND_SEED: &[u8] = b"GIVEAWAY_ROUND_SEED";
+
+pub const LOCK_SEED: &[u8] = b"LOCK_SEED";
+
+pub const POSITION_MINT_SEED: &[u8] = b"POSITION_MINT_SEED";
+
+pub const QUEUED_ACTION_SEED: &[u8] = b"QUEUED_ACTION_SEED";
+
+// the pool set up by `initialize`, its authority creates the other pools
+pub const ROOT_POOL_ID: u64 = 0;
+
+// deposit mint of SOL pools
+pub const NATIVE_MINT: Pubkey = Pubkey::new_from_array([0; 32]);
+
 pub const SOL_PER_BEAN: u64 = 1000;
 pub const SECONDS_PER_DAY: u64 = 86400;
-pub const DEPOSIT_FEE: u64 = 1;
-pub const AIRDROP_FEE: u64 = 1;
-pub const WITHDRAWAL_FEE: u64 = 5;
-pub const DEV_FEE: u64 = 10;
-pub const MARKETING_FEE: u64 = 19;
-pub const CEO_FEE: u64 = 66;
-pub const REF_BONUS: u64 = 5;
-pub const FIRST_DEPOSIT_REF_BONUS: u64 = 5;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+// fees and bonuses are in basis points
+pub const DEPOSIT_FEE: u64 = 100;
+pub const AIRDROP_FEE: u64 = 100;
+pub const WITHDRAWAL_FEE: u64 = 500;
+pub const DEV_FEE: u64 = 1000;
+pub const MARKETING_FEE: u64 = 1900;
+pub const CEO_FEE: u64 = 6600;
+pub const REF_BONUS: u64 = 500;
+pub const FIRST_DEPOSIT_REF_BONUS: u64 = 500;
+pub const MAX_CRANK_TIP: u64 = 100;
+// a cycle pays out up to 3x its deposits by default
+pub const DEFAULT_MAX_PAYOUT_MULTIPLIER: u64 = 30_000;
 
+// limits below are valued in beans at SOL_PER_BEAN, whatever the pool's price
 pub const MIN_DEPOSIT: u64 = 10_000_000; // 0.01 SOL
 pub const MIN_BAKE: u64 = 10_000_000; // 0.01 SOL
 pub const MAX_WALLET_TVL_IN_SOL: u64 = 200_000_000_000; // 200 SOL
 pub const MAX_DAILY_REWARDS_IN_SOL: u64 = 5_000_000_000; // 5 SOL
 pub const MIN_REF_DEPOSIT_FOR_BONUS: u64 = 500_000_000; // 0.5 SOL
+pub const MIN_AUTO_BAKE_INTERVAL: u64 = 3600; // 1 hour
+pub const MAX_WHITELISTED_RECIPIENTS: usize = 5;
+pub const MAX_FEE_RECIPIENTS: usize = 10;
+pub const MAX_RELAYERS: usize = 3;
+pub const MAX_LOCKS: u64 = 10;
+pub const MAX_ADMIN_DELAY: u64 = 30 * 86400; // 30 days
+// a vault migration waits at least this long, whatever the admin delay
+pub const MIN_MIGRATION_DELAY: u64 = 2 * 86400; // 2 days
+pub const MAX_ADMINS: usize = 5;
+pub const MAX_EPOCH_LENGTH: u64 = 365 * 86400; // 1 year
+pub const MAX_EAT_COOLDOWN: u64 = 30 * 86400; // 30 days
+
+pub const MAX_TAX_TABLE_DAYS: usize = 30;
+pub const TAX_CURVE_TABLE: u8 = 0;
+pub const TAX_CURVE_LINEAR: u8 = 1;
+pub const TAX_CURVE_EXPONENTIAL: u8 = 2;
+
+pub const GIVEAWAY_TICKET_DEPOSIT: u64 = 1_000_000_000; // 1 SOL per ticket
+pub const MAX_GIVEAWAY_PARTICIPANTS: usize = 100;
+// after the reveal deadline, time the authority has to draw before anyone can draw without its secret
+pub const GIVEAWAY_DRAW_GRACE_PERIOD: u64 = 3 * 86400;
+
+// lock periods in days and the daily reward boost they earn, in basis points
+pub const LOCK_BOOSTS: [(u64, u64); 3] = [(30, 50), (60, 100), (90, 200)];
@@ -38,5 +38,171 @@ pub enum BeanError {
     #[msg("You have reached max payout")]
     MaxPayoutReached,
 
-    
+    #[msg("Auto bake is not enabled for this user")]
+    AutoBakeDisabled,
+
+    #[msg("Auto bake interval has not passed yet")]
+    AutoBakeNotDue,
+
+    #[msg("Invalid auto bake settings")]
+    InvalidAutoBakeSettings,
+
+    #[msg("Signer is neither the user nor an allowed delegate")]
+    NotAllowedDelegate,
+
+    #[msg("Recipient is not allowed for this user")]
+    RecipientNotAllowed,
+
+    #[msg("Too many whitelisted recipients")]
+    TooManyRecipients,
+
+    #[msg("Amount exceeds pending rewards")]
+    ExceedsPendingRewards,
+
+    #[msg("Invalid tax schedule")]
+    InvalidTaxSchedule,
+
+    #[msg("Too many fee recipients")]
+    TooManyFeeRecipients,
+
+    #[msg("Fee recipient already exists")]
+    FeeRecipientExists,
+
+    #[msg("Fee recipient not found")]
+    FeeRecipientNotFound,
+
+    #[msg("Fee weights exceed 100%")]
+    InvalidFeeWeights,
+
+    #[msg("Fee recipient has unclaimed fees")]
+    UnclaimedFees,
+
+    #[msg("Giveaway round is closed")]
+    GiveawayRoundClosed,
+
+    #[msg("Giveaway round is still open")]
+    GiveawayRoundOpen,
+
+    #[msg("Not eligible for the giveaway")]
+    NotEligibleForGiveaway,
+
+    #[msg("Giveaway round is full")]
+    GiveawayFull,
+
+    #[msg("Secret doesn't match the commitment")]
+    InvalidGiveawaySecret,
+
+    #[msg("Giveaway round is already drawn")]
+    GiveawayAlreadyDrawn,
+
+    #[msg("Incorrect giveaway winner")]
+    IncorrectGiveawayWinner,
+
+    #[msg("Invalid token account")]
+    InvalidTokenAccount,
+
+    #[msg("Not supported by this pool")]
+    UnsupportedPool,
+
+    #[msg("User state still holds beans")]
+    UserStateNotClosable,
+
+    #[msg("Payer is not allowed to create this user state")]
+    PayerNotAllowed,
+
+    #[msg("Too many relayers")]
+    TooManyRelayers,
+
+    #[msg("Max payout multiplier is below 1x")]
+    InvalidPayoutMultiplier,
+
+    #[msg("Deposit would take the wallet over its beans cap")]
+    DepositExceedsWalletTvl,
+
+    #[msg("Pool TVL cap reached")]
+    GlobalTvlReached,
+
+    #[msg("Deposit limit of this epoch reached")]
+    EpochDepositLimitReached,
+
+    #[msg("Withdrawal limit of this epoch reached")]
+    EpochWithdrawalLimitReached,
+
+    #[msg("Too soon since the last eat")]
+    EatCooldownActive,
+
+    #[msg("Daily withdrawal limit of the user reached")]
+    UserWithdrawalLimitReached,
+
+    #[msg("Deposits are not open yet")]
+    NotLaunched,
+
+    #[msg("Not on the whitelist")]
+    NotWhitelisted,
+
+    #[msg("Invalid launch schedule")]
+    InvalidLaunchSchedule,
+
+    #[msg("All of the user's lock positions must be passed")]
+    MissingLockPositions,
+
+    #[msg("Invalid lock position")]
+    InvalidLockPosition,
+
+    #[msg("Lock period must be 30, 60 or 90 days")]
+    InvalidLockPeriod,
+
+    #[msg("Too many lock positions")]
+    TooManyLocks,
+
+    #[msg("Lock position is still locked")]
+    StillLocked,
+
+    #[msg("Position is held as an NFT")]
+    PositionTokenized,
+
+    #[msg("Not the holder of the position NFT")]
+    NotPositionHolder,
+
+    #[msg("Admin changes must be queued through the timelock")]
+    TimelockActive,
+
+    #[msg("Admin delay is too long")]
+    InvalidAdminDelay,
+
+    #[msg("Queued action is not executable yet")]
+    ActionNotReady,
+
+    #[msg("Pool is paused")]
+    PoolPaused,
+
+    #[msg("Invalid admin set")]
+    InvalidAdmins,
+
+    #[msg("Already approved")]
+    AlreadyApproved,
+
+    #[msg("Not enough admin approvals")]
+    NotEnoughApprovals,
+
+    #[msg("Pool must be paused")]
+    PoolNotPaused,
+
+    #[msg("Vault can only migrate to the vault of another pool")]
+    InvalidMigrationTarget,
+
+    #[msg("Pool's vault has migrated")]
+    PoolMigrated,
+
+    #[msg("Lock positions must be unlocked first")]
+    LocksStillOpen,
+
+    #[msg("Previous giveaway round is not drawn")]
+    GiveawayNotDrawn,
+
+    #[msg("Invalid pool limits")]
+    InvalidPoolLimits,
+
+    #[msg("Pool's vault has not migrated")]
+    PoolNotMigrated,
 }
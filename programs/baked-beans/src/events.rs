@@ -1,7 +1,9 @@
+use crate::states::*;
 use anchor_lang::prelude::*;
 
 #[event]
 pub struct EventBoughtBeans {
+    pub pool: Pubkey,
     pub user_address: Pubkey,
     pub ref_address: Pubkey,
     pub sol_amount: u64,
@@ -12,6 +14,7 @@ pub struct EventBoughtBeans {
 
 #[event]
 pub struct EventBaked {
+    pub pool: Pubkey,
     pub user_address: Pubkey,
     pub ref_address: Pubkey,
     pub beans_from: u64,
@@ -21,7 +24,306 @@ pub struct EventBaked {
 
 #[event]
 pub struct EventAte {
+    pub pool: Pubkey,
     pub user_address: Pubkey,
+    pub recipient: Pubkey,
     pub sol_to_eat: u64,
     pub beans_before_fee: u64
-}
\ No newline at end of file
+}
+
+
+#[event]
+pub struct EventAutoBakeSet {
+    pub user_address: Pubkey,
+    pub interval: u64,
+    pub tip: u64
+}
+
+
+#[event]
+pub struct EventDelegateSet {
+    pub user_address: Pubkey,
+    pub delegate: Pubkey,
+    pub can_eat: u8
+}
+
+
+#[event]
+pub struct EventDelegateRevoked {
+    pub user_address: Pubkey,
+    pub delegate: Pubkey
+}
+
+
+#[event]
+pub struct EventRecipientWhitelistSet {
+    pub user_address: Pubkey,
+    pub recipients: Vec<Pubkey>
+}
+
+
+#[event]
+pub struct EventHarvested {
+    pub pool: Pubkey,
+    pub user_address: Pubkey,
+    pub recipient: Pubkey,
+    pub beans_baked: u64,
+    pub beans_eaten: u64,
+    pub sol_to_eat: u64,
+    pub beans_from: u64,
+    pub beans_to: u64
+}
+
+
+#[event]
+pub struct EventTaxScheduleSet {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub tax_schedule: TaxSchedule
+}
+
+
+#[event]
+pub struct EventEatQuote {
+    pub user_address: Pubkey,
+    pub pending_beans: u64,
+    pub days_since_last_eat: u64,
+    pub tax: u64,
+    pub sol_to_eat: u64,
+    // 0 when the user can eat now
+    pub next_eat_at: u64,
+    pub withdrawal_allowance: u64
+}
+
+
+#[event]
+pub struct EventFeeScheduleUpdated {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub recipients: Vec<FeeRecipient>
+}
+
+
+#[event]
+pub struct EventFeesClaimed {
+    pub pool: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64
+}
+
+
+#[event]
+pub struct EventGiveawayRoundStarted {
+    pub pool: Pubkey,
+    pub round_id: u64,
+    pub end_time: u64,
+    pub reveal_deadline: u64,
+    pub authority_commitment: [u8; 32]
+}
+
+
+#[event]
+pub struct EventGiveawayEntered {
+    pub pool: Pubkey,
+    pub round_id: u64,
+    pub user_address: Pubkey,
+    pub tickets: u64
+}
+
+
+#[event]
+pub struct EventGiveawaySecretRevealed {
+    pub pool: Pubkey,
+    pub round_id: u64,
+    pub user_address: Pubkey
+}
+
+
+#[event]
+pub struct EventGiveawayDrawn {
+    pub pool: Pubkey,
+    pub round_id: u64,
+    pub seed: [u8; 32],
+    pub total_tickets: u64,
+    pub winner: Pubkey,
+    pub prize: u64
+}
+
+
+#[event]
+pub struct EventPoolCreated {
+    pub pool: Pubkey,
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    pub authority: Pubkey
+}
+
+
+#[event]
+pub struct EventUserStateClosed {
+    pub pool: Pubkey,
+    pub user_address: Pubkey,
+    pub upline: Pubkey
+}
+
+
+#[event]
+pub struct EventUserInitPolicySet {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub restrict_user_init: u8,
+    pub relayers: Vec<Pubkey>
+}
+
+
+#[event]
+pub struct EventCycleStarted {
+    pub pool: Pubkey,
+    pub user_address: Pubkey,
+    pub cycle: u64
+}
+
+
+#[event]
+pub struct EventMaxPayoutMultiplierSet {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub max_payout_multiplier: u64
+}
+
+
+#[event]
+pub struct EventPoolLimitsSet {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub limits: PoolLimits
+}
+
+
+#[event]
+pub struct EventLaunchScheduleSet {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub launch_time: u64,
+    pub whitelist_end: u64,
+    pub whitelist_root: [u8; 32]
+}
+
+
+#[event]
+pub struct EventBeansLocked {
+    pub pool: Pubkey,
+    pub user_address: Pubkey,
+    pub lock_id: u64,
+    pub beans: u64,
+    pub boost: u64,
+    pub unlock_at: u64
+}
+
+
+#[event]
+pub struct EventBeansUnlocked {
+    pub pool: Pubkey,
+    pub user_address: Pubkey,
+    pub lock_id: u64,
+    pub beans: u64
+}
+
+
+#[event]
+pub struct EventPositionTokenized {
+    pub pool: Pubkey,
+    pub user_address: Pubkey,
+    pub position_mint: Pubkey
+}
+
+
+#[event]
+pub struct EventAdminActionQueued {
+    pub pool: Pubkey,
+    pub action_id: u64,
+    pub proposer: Pubkey,
+    pub action: AdminAction,
+    pub eta: u64
+}
+
+
+#[event]
+pub struct EventAdminActionExecuted {
+    pub pool: Pubkey,
+    pub action_id: u64,
+    pub action: AdminAction
+}
+
+
+#[event]
+pub struct EventAdminActionCancelled {
+    pub pool: Pubkey,
+    pub action_id: u64
+}
+
+
+#[event]
+pub struct EventAdminDelaySet {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub admin_delay: u64
+}
+
+
+#[event]
+pub struct EventPausedSet {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub paused: u8
+}
+
+
+#[event]
+pub struct EventAdminActionApproved {
+    pub pool: Pubkey,
+    pub action_id: u64,
+    pub admin: Pubkey,
+    pub approvals: u8
+}
+
+
+#[event]
+pub struct EventAdminsSet {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub admins: Vec<Pubkey>,
+    pub admin_threshold: u8
+}
+
+
+#[event]
+pub struct EventReconfigured {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub recipients: Vec<FeeRecipient>
+}
+
+
+#[event]
+pub struct EventVaultMigrated {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub action_id: u64,
+    pub target_pool: Pubkey,
+    pub old_vault: Pubkey,
+    pub new_vault: Pubkey,
+    pub amount: u64,
+    pub giveaway_amount: u64
+}
+
+
+#[event]
+pub struct EventUserStateMigrated {
+    pub pool: Pubkey,
+    pub target_pool: Pubkey,
+    pub user_address: Pubkey,
+    pub beans: u64,
+    pub accrued_beans: u64
+}
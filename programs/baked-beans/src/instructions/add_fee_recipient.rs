@@ -0,0 +1,42 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AddFeeRecipient<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+}
+
+impl<'info> AddFeeRecipient<'info> {
+    pub fn validate(&self, account: Pubkey, weight: u64) -> Result<()> {
+        check_add_fee_recipient(&self.fee_schedule, account, weight)?;
+        Ok(())
+    }
+}
+
+#[access_control(ctx.accounts.validate(account, weight))]
+pub fn handle(ctx: Context<AddFeeRecipient>, account: Pubkey, weight: u64) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.fee_schedule.recipients.push(FeeRecipient { account, weight, accrued: 0 });
+
+    emit!(EventFeeScheduleUpdated {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      recipients: accts.fee_schedule.recipients.clone()
+    });
+    Ok(())
+}
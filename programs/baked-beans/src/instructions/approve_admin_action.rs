@@ -0,0 +1,49 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ApproveAdminAction<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = is_admin(&global_state, admin.key()) @ BeanError::NotAllowedAuthority,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [QUEUED_ACTION_SEED, global_state.key().as_ref(), &queued_action.action_id.to_le_bytes()],
+        bump = queued_action.bump,
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+}
+
+impl<'info> ApproveAdminAction<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.queued_action.approvals.contains(&self.admin.key()),
+            BeanError::AlreadyApproved
+        );
+        Ok(())
+    }
+}
+
+/// Add the signing admin's approval to a queued admin action
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<ApproveAdminAction>) -> Result<()> {
+    let accts = ctx.accounts;
+    // approvals of removed admins no longer count, drop them to keep room for current ones
+    let global_state = &accts.global_state;
+    accts.queued_action.approvals.retain(|approver| is_admin(global_state, *approver));
+    accts.queued_action.approvals.push(accts.admin.key());
+
+    emit!(EventAdminActionApproved {
+      pool: accts.global_state.key(),
+      action_id: accts.queued_action.action_id,
+      admin: accts.admin.key(),
+      approvals: approval_count(&accts.global_state, &accts.queued_action)
+    });
+    Ok(())
+}
@@ -4,46 +4,62 @@ use anchor_lang::prelude::*;
 #[derive(Accounts)]
 pub struct BakeBeans<'info> {
     #[account(mut)]
+    // the user or their delegate
     pub user: Signer<'info>,
     #[account(
       mut,
-      seeds = [GLOBAL_STATE_SEED],
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
       bump,
     )]
     pub global_state: Account<'info, GlobalState>,
 
     #[account(
         mut,
-        seeds = [USER_STATE_SEED, user.key().as_ref()],
-        bump,
-        has_one = user,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user_state.user.as_ref()],
+        bump = user_state.bump,
     )]
     pub user_state: Account<'info, UserState>,
 
+    /// CHECK: holder's token account of the position NFT, ignored for positions without one
+    pub position_token: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handle(ctx: Context<BakeBeans>, only_rebaking: u8) -> Result<()> {
+impl<'info> BakeBeans<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let owner = position_owner(&self.user_state, &self.position_token)?;
+        require!(
+            is_owner_or_delegate(&self.user_state, owner, self.user.key()),
+            BeanError::NotAllowedDelegate
+        );
+        Ok(())
+    }
+}
+
+/// Compound pending rewards, the user's lock positions are passed as remaining accounts
+#[access_control(ctx.accounts.validate())]
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, BakeBeans<'info>>, only_rebaking: u8) -> Result<()> {
     let cur_timestamp = Clock::get()?.unix_timestamp as u64;
     let accts = ctx.accounts;
-    require!(max_tvl_reached(&accts.user_state) == false, BeanError::WalletTvlReached);
-    require!(accts.user_state.total_deposit > 0, BeanError::InvalidAction);
+    let mut locks = load_locks(ctx.remaining_accounts, &accts.user_state, accts.user_state.active_locks)?;
+    let mut positions = lock_positions(&locks);
+    let beans_from_rewards = settle_locks(&accts.user_state, &mut positions, cur_timestamp);
     if only_rebaking == 1 {
       require!(
-        beans_to_sol(rewarded_beans(&accts.user_state)) > MIN_BAKE, 
+        beans_from_rewards > sol_to_beans(MIN_BAKE),
         BeanError::UnderMinBake
       );
     }
 
     let beans_from = accts.user_state.beans;
-    let beans_from_rewards = rewarded_beans(&accts.user_state);
-    let total_beans = add_beans(&accts.user_state, beans_from_rewards);
-    accts.user_state.beans = total_beans;
-    accts.user_state.baked_at = cur_timestamp;
+    process_bake(&mut accts.user_state, beans_from_rewards, cur_timestamp)?;
+    store_locks(&mut locks, &positions, ctx.program_id)?;
 
     emit!(EventBaked {
-      user_address: accts.user.key(),
+      pool: accts.global_state.key(),
+      user_address: accts.user_state.user,
       ref_address: accts.user_state.upline,
       beans_from,
       beans_to: accts.user_state.beans
@@ -51,3 +67,14 @@ pub fn handle(ctx: Context<BakeBeans>, only_rebaking: u8) -> Result<()> {
 
     Ok(())
 }
+
+/// Compound pending rewards into beans, shared by `bake_beans` and `crank_bake`
+pub fn process_bake(user_state: &mut UserState, beans_from_rewards: u64, cur_timestamp: u64) -> Result<()> {
+    require!(!max_tvl_reached(user_state), BeanError::WalletTvlReached);
+    require!(user_state.total_deposit > 0, BeanError::InvalidAction);
+
+    user_state.beans = add_beans(user_state, beans_from_rewards);
+    user_state.accrued_beans = 0;
+    user_state.baked_at = cur_timestamp;
+    Ok(())
+}
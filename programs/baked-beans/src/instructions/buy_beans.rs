@@ -1,6 +1,7 @@
 use crate::{constants::*, error::*, states::*, utils::*, events::*};
 use anchor_lang::prelude::*;
 use solana_program::{program::invoke, system_instruction};
+use std::mem::size_of;
 
 #[derive(Accounts)]
 #[instruction(ref_user: Pubkey)]
@@ -10,22 +11,19 @@ pub struct BuyBeans<'info> {
 
     #[account(
       mut,
-      seeds = [GLOBAL_STATE_SEED],
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
       bump,
+      constraint = is_native_pool(&global_state) @ BeanError::UnsupportedPool,
+      constraint = global_state.paused == 0 @ BeanError::PoolPaused,
     )]
     pub global_state: Account<'info, GlobalState>,
 
-    #[account(mut, address = global_state.dev_account)]
-    /// CHECK: this should be set by admin
-    pub dev_account: AccountInfo<'info>,
-
-    #[account(mut, address = global_state.marketing_account)]
-    /// CHECK: this should be set by admin
-    pub marketing_account: AccountInfo<'info>,
-
-    #[account(mut, address = global_state.ceo_account)]
-    /// CHECK: this should be set by admin
-    pub ceo_account: AccountInfo<'info>,
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
 
     #[account(
         mut,
@@ -34,17 +32,20 @@ pub struct BuyBeans<'info> {
     /// CHECK: this should be set by admin
     pub vault: AccountInfo<'info>,
 
+    // created on the user's first buy if init_user_state wasn't called
     #[account(
-        mut,
-        seeds = [USER_STATE_SEED, user.key().as_ref()],
+        init_if_needed,
+        payer = user,
+        space = 8 + size_of::<UserState>() + 32 * 100 + 32 * MAX_WHITELISTED_RECIPIENTS,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user.key().as_ref()],
         bump,
     )]
     pub user_state: Account<'info, UserState>,
 
     #[account(
       mut,
-      seeds = [USER_STATE_SEED, ref_user.key().as_ref()],
-      bump,
+      seeds = [USER_STATE_SEED, global_state.key().as_ref(), ref_user.key().as_ref()],
+      bump = ref_user_state.bump,
       constraint = user_state.user != ref_user_state.user
     )]
     pub ref_user_state: Account<'info, UserState>,
@@ -53,87 +54,31 @@ pub struct BuyBeans<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handle(ctx: Context<BuyBeans>, ref_user: Pubkey, sol_amount: u64) -> Result<()> {
+pub fn handle(ctx: Context<BuyBeans>, ref_user: Pubkey, sol_amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
     let accts = ctx.accounts;
     let user_key = accts.user.key();
-    require!(sol_amount >= MIN_DEPOSIT, BeanError::InsufficientDeposit);
-    require!(accts.user_state.total_deposit <= MAX_WALLET_TVL_IN_SOL, BeanError::TotalDepositReached);
-    require!(ref_user.eq(&accts.global_state.authority) || accts.ref_user_state.total_deposit > 0, BeanError::ReferrerShouldInvest);
-
-    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
-
-    let beans_from = accts.user_state.beans;
-    let total_sol_fee = percent_from_amount(sol_amount, DEPOSIT_FEE);
-    let sol_value = sol_amount - total_sol_fee;
-    let beans_bought = sol_to_beans(sol_value);
-
-    let total_beans_bought = add_beans(&accts.user_state, beans_bought);
-    accts.user_state.beans = total_beans_bought;
-
-    // referrer
-    if accts.user_state.has_referred == 0 {
-      accts.user_state.has_referred = 1;
-      accts.user_state.upline = ref_user;
-      accts.ref_user_state.referrals.push(user_key);
-      if accts.user_state.total_deposit == 0 {
-        let ref_bonus = percent_from_amount(sol_to_beans(sol_amount), FIRST_DEPOSIT_REF_BONUS);
-        accts.ref_user_state.beans = add_beans(&accts.ref_user_state, ref_bonus);
-      }
+    if accts.user_state.user.eq(&Pubkey::default()) {
+      accts.user_state.user = user_key;
+      accts.user_state.pool = accts.global_state.key();
+      accts.user_state.bump = *ctx.bumps.get("user_state").unwrap();
     }
-    
-    if accts.user_state.total_deposit == 0 {
-      accts.user_state.first_deposit_time = cur_timestamp;
-      accts.global_state.total_bakers = accts.global_state.total_bakers + 1;
-    }
-
-    accts.user_state.total_deposit = accts.user_state.total_deposit + sol_amount;
-
-    if 
-      accts.user_state.has_referred == 1 &&
-      accts.user_state.total_deposit  >= MIN_REF_DEPOSIT_FOR_BONUS &&
-      ref_exists(&accts.ref_user_state, user_key) == false
-    {
-        accts.ref_user_state.bonus_eligible_referrals.push(user_key);
-    }
-
-    // fee distribution
-    let dev_fee = percent_from_amount(total_sol_fee, DEV_FEE);
-    let market_fee = percent_from_amount(total_sol_fee, MARKETING_FEE);
-    let ceo_fee = percent_from_amount(total_sol_fee, CEO_FEE);
 
-    let remained_fee = total_sol_fee - dev_fee - market_fee - ceo_fee;
-
-    // send dev_fee
-    invoke(
-        &system_instruction::transfer(&user_key, &accts.dev_account.key(), dev_fee),
-        &[
-            accts.user.to_account_info().clone(),
-            accts.dev_account.clone(),
-            accts.system_program.to_account_info().clone(),
-        ],
-    )?;
-    // send marketing_fee
-    invoke(
-          &system_instruction::transfer(&user_key, &accts.marketing_account.key(), market_fee),
-          &[
-              accts.user.to_account_info().clone(),
-              accts.marketing_account.clone(),
-              accts.system_program.to_account_info().clone(),
-          ],
-      )?;
-    // send ceo_fee
-    invoke(
-        &system_instruction::transfer(&user_key, &accts.ceo_account.key(), ceo_fee),
-        &[
-            accts.user.to_account_info().clone(),
-            accts.ceo_account.clone(),
-            accts.system_program.to_account_info().clone(),
-        ],
+    check_launch(&accts.global_state, user_key, &proof, cur_timestamp)?;
+    let beans_from = accts.user_state.beans;
+    process_buy(
+      &mut accts.global_state,
+      &mut accts.fee_schedule,
+      &mut accts.user_state,
+      &mut accts.ref_user_state,
+      ref_user,
+      sol_amount,
+      cur_timestamp,
     )?;
 
-    // add vault <- sol_amount - fee
+    // add vault <- sol_amount
     invoke(
-      &system_instruction::transfer(&user_key, &accts.vault.key(), sol_value + remained_fee),
+      &system_instruction::transfer(&user_key, &accts.vault.key(), sol_amount),
       &[
           accts.user.to_account_info().clone(),
           accts.vault.clone(),
@@ -143,7 +88,8 @@ pub fn handle(ctx: Context<BuyBeans>, ref_user: Pubkey, sol_amount: u64) -> Resu
 
     // todo: handleBake, emit Event
     emit!(EventBoughtBeans {
-      user_address: accts.user.key(),
+      pool: accts.global_state.key(),
+      user_address: user_key,
       ref_address: ref_user,
       sol_amount,
       beans_from,
@@ -151,3 +97,88 @@ pub fn handle(ctx: Context<BuyBeans>, ref_user: Pubkey, sol_amount: u64) -> Resu
     });
     Ok(())
 }
+
+/// Credit a deposit of `amount`, in the pool's units, to the user and their referrer
+/// and accrue its fee. Shared by `buy_beans` and `buy_beans_token`,
+/// the caller moves the funds into the vault
+pub fn process_buy(
+    global_state: &mut GlobalState,
+    fee_schedule: &mut FeeSchedule,
+    user_state: &mut UserState,
+    ref_user_state: &mut UserState,
+    ref_user: Pubkey,
+    amount: u64,
+    cur_timestamp: u64,
+) -> Result<()> {
+    let user_key = user_state.user;
+    let price = global_state.price_per_bean;
+    require!(amount_to_beans(amount, price) >= sol_to_beans(MIN_DEPOSIT), BeanError::InsufficientDeposit);
+    if user_state.cycle_deposit > 0 && max_payout_reached(global_state, user_state) {
+      // locked beans and their held rewards belong to the cycle, unlock them before the next one
+      require!(user_state.active_locks == 0, BeanError::LocksStillOpen);
+      start_new_cycle(user_state, cur_timestamp);
+      emit!(EventCycleStarted {
+        pool: user_state.pool,
+        user_address: user_key,
+        cycle: user_state.cycle
+      });
+    }
+    // the incoming deposit has to fit under the cap, rather than being taken and clamped
+    require!(
+      amount_to_beans(user_state.cycle_deposit + amount, price) <= sol_to_beans(MAX_WALLET_TVL_IN_SOL),
+      BeanError::TotalDepositReached
+    );
+    require!(ref_user.eq(&global_state.authority) || ref_user_state.total_deposit > 0, BeanError::ReferrerShouldInvest);
+
+    record_deposit(global_state, amount, cur_timestamp)?;
+
+    let total_fee = bps_from_amount_ceil(amount, DEPOSIT_FEE);
+    let beans_bought = amount_to_beans(amount - total_fee, price);
+    require!(
+      user_state.beans + user_state.locked_beans + beans_bought <= sol_to_beans(MAX_WALLET_TVL_IN_SOL),
+      BeanError::DepositExceedsWalletTvl
+    );
+    user_state.beans += beans_bought;
+
+    // referrer
+    if user_state.has_referred == 0 {
+      user_state.has_referred = 1;
+      user_state.upline = ref_user;
+      // a user who closed and re-created their state is still listed and earns no second bonus
+      if !ref_user_state.referrals.contains(&user_key) {
+        ref_user_state.referrals.push(user_key);
+        let ref_bonus = bps_from_amount(amount_to_beans(amount, price), FIRST_DEPOSIT_REF_BONUS);
+        ref_user_state.beans = add_beans(ref_user_state, ref_bonus);
+      }
+    }
+
+    if user_state.total_deposit == 0 {
+      user_state.first_deposit_time = cur_timestamp;
+      global_state.total_bakers += 1;
+    }
+
+    user_state.total_deposit += amount;
+    user_state.cycle_deposit += amount;
+
+    // deposits during an open giveaway round earn tickets
+    if cur_timestamp <= global_state.giveaway_round_end {
+      if user_state.giveaway_round != global_state.giveaway_round {
+        user_state.giveaway_round = global_state.giveaway_round;
+        user_state.giveaway_deposit = 0;
+      }
+      user_state.giveaway_deposit += amount_to_beans(amount, price);
+    }
+
+    if
+      user_state.has_referred == 1 &&
+      amount_to_beans(user_state.total_deposit, price) >= sol_to_beans(MIN_REF_DEPOSIT_FOR_BONUS) &&
+      !ref_exists(ref_user_state, user_key)
+    {
+        ref_user_state.bonus_eligible_referrals.push(user_key);
+    }
+
+    // fees accrue to the fee schedule and are claimed from the vault later
+    let remained_fee = accrue_fees(fee_schedule, total_fee);
+    global_state.retained_fees += remained_fee;
+    Ok(())
+}
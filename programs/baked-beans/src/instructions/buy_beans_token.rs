@@ -0,0 +1,93 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*, instructions::buy_beans::process_buy};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(ref_user: Pubkey)]
+pub struct BuyBeansToken<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = !is_native_pool(&global_state) @ BeanError::UnsupportedPool,
+      constraint = global_state.paused == 0 @ BeanError::PoolPaused,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(mut)]
+    /// CHECK: the user's token account, the token program checks it is theirs
+    pub user_token_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        address = global_state.vault
+    )]
+    /// CHECK: the pool's token vault
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+      mut,
+      seeds = [USER_STATE_SEED, global_state.key().as_ref(), ref_user.key().as_ref()],
+      bump = ref_user_state.bump,
+      constraint = user_state.user != ref_user_state.user
+    )]
+    pub ref_user_state: Account<'info, UserState>,
+
+    #[account(address = spl_token::ID)]
+    /// CHECK: spl token program
+    pub token_program: AccountInfo<'info>,
+}
+
+/// `buy_beans` for token pools, `amount` is in base units of the pool's mint
+pub fn handle(ctx: Context<BuyBeansToken>, ref_user: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let accts = ctx.accounts;
+    let user_key = accts.user.key();
+
+    check_launch(&accts.global_state, user_key, &proof, cur_timestamp)?;
+    let beans_from = accts.user_state.beans;
+    process_buy(
+      &mut accts.global_state,
+      &mut accts.fee_schedule,
+      &mut accts.user_state,
+      &mut accts.ref_user_state,
+      ref_user,
+      amount,
+      cur_timestamp,
+    )?;
+
+    // add vault <- amount
+    transfer_tokens(
+      &accts.token_program,
+      &accts.user_token_account,
+      &accts.vault,
+      &accts.user.to_account_info(),
+      amount,
+      &[],
+    )?;
+
+    emit!(EventBoughtBeans {
+      pool: accts.global_state.key(),
+      user_address: user_key,
+      ref_address: ref_user,
+      sol_amount: amount,
+      beans_from,
+      beans_to: accts.user_state.beans
+    });
+    Ok(())
+}
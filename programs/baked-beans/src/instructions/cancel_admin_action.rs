@@ -0,0 +1,52 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CancelAdminAction<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = is_admin(&global_state, admin.key()) @ BeanError::NotAllowedAuthority,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [QUEUED_ACTION_SEED, global_state.key().as_ref(), &queued_action.action_id.to_le_bytes()],
+        bump = queued_action.bump,
+        close = proposer,
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(mut, address = queued_action.proposer)]
+    /// CHECK: refunded the proposal's rent
+    pub proposer: AccountInfo<'info>,
+}
+
+impl<'info> CancelAdminAction<'info> {
+    pub fn validate(&self) -> Result<()> {
+        // a single admin can't veto the others' proposals
+        require!(self.admin.key().eq(&self.queued_action.proposer), BeanError::NotAllowedAuthority);
+        if let AdminAction::SetAdmins { admins, .. } = &self.queued_action.action {
+          require!(
+            self.global_state.admin_threshold == 0 || admins.contains(&self.admin.key()),
+            BeanError::NotAllowedAuthority
+          );
+        }
+        Ok(())
+    }
+}
+
+/// Drop a queued admin action before it executes, only its proposer can.
+/// An admin set change removing the proposer can't be withdrawn
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<CancelAdminAction>) -> Result<()> {
+    let accts = ctx.accounts;
+    emit!(EventAdminActionCancelled {
+      pool: accts.global_state.key(),
+      action_id: accts.queued_action.action_id
+    });
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke_signed, system_instruction};
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = is_native_pool(&global_state) @ BeanError::UnsupportedPool,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: this should be checked with address in global_state
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: checked against the fee schedule, fees are only ever sent here
+    pub recipient: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimFees<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.fee_schedule.recipients.iter().any(|recipient| recipient.account.eq(self.recipient.key)),
+            BeanError::FeeRecipientNotFound
+        );
+        Ok(())
+    }
+}
+
+/// Send a fee recipient its accrued fees from the vault.
+/// Anyone can call this, fees always go to the recipient
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<ClaimFees>) -> Result<()> {
+    let accts = ctx.accounts;
+    let recipient_key = accts.recipient.key();
+
+    let mut amount = 0;
+    for recipient in accts.fee_schedule.recipients.iter_mut() {
+      if recipient.account.eq(&recipient_key) {
+        amount += recipient.accrued;
+        recipient.accrued = 0;
+      }
+    }
+    require!(amount > 0, BeanError::InsufficientAmount);
+    release_tvl(&mut accts.global_state, amount);
+
+    let bump = ctx.bumps.get("vault").unwrap();
+    let pool = accts.global_state.key();
+    invoke_signed(
+        &system_instruction::transfer(&accts.vault.key(), &recipient_key, amount),
+        &[
+            accts.vault.to_account_info().clone(),
+            accts.recipient.clone(),
+            accts.system_program.to_account_info().clone(),
+        ],
+        &[&[VAULT_SEED, pool.as_ref(), &[*bump]]],
+    )?;
+
+    // lamports should be bigger than zero to prevent rent exemption
+    let rent = Rent::default();
+    let required_lamports = rent
+        .minimum_balance(0)
+        .max(1)
+        .saturating_sub(accts.vault.to_account_info().lamports());
+    require!(
+        **accts.vault.lamports.borrow() > required_lamports,
+        BeanError::InsufficientAmount
+    );
+
+    emit!(EventFeesClaimed {
+      pool: accts.global_state.key(),
+      recipient: recipient_key,
+      amount
+    });
+    Ok(())
+}
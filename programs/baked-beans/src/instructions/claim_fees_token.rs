@@ -0,0 +1,86 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ClaimFeesToken<'info> {
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = !is_native_pool(&global_state) @ BeanError::UnsupportedPool,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: owner of the token vault
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(mut, address = global_state.vault)]
+    /// CHECK: the pool's token vault
+    pub vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: a token account of the pool's mint owned by a fee recipient
+    pub recipient: AccountInfo<'info>,
+
+    #[account(address = spl_token::ID)]
+    /// CHECK: spl token program
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> ClaimFeesToken<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let recipient = load_token_account(&self.recipient)?;
+        require!(recipient.mint.eq(&self.global_state.mint), BeanError::InvalidTokenAccount);
+        require!(
+            self.fee_schedule.recipients.iter().any(|fee_recipient| fee_recipient.account.eq(&recipient.owner)),
+            BeanError::FeeRecipientNotFound
+        );
+        Ok(())
+    }
+}
+
+/// `claim_fees` for token pools, the recipient is the owner of the token account
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<ClaimFeesToken>) -> Result<()> {
+    let accts = ctx.accounts;
+    let recipient_key = load_token_account(&accts.recipient)?.owner;
+
+    let mut amount = 0;
+    for recipient in accts.fee_schedule.recipients.iter_mut() {
+      if recipient.account.eq(&recipient_key) {
+        amount += recipient.accrued;
+        recipient.accrued = 0;
+      }
+    }
+    require!(amount > 0, BeanError::InsufficientAmount);
+    release_tvl(&mut accts.global_state, amount);
+
+    let bump = ctx.bumps.get("vault_authority").unwrap();
+    let pool = accts.global_state.key();
+    transfer_tokens(
+      &accts.token_program,
+      &accts.vault,
+      &accts.recipient,
+      &accts.vault_authority,
+      amount,
+      &[&[VAULT_SEED, pool.as_ref(), &[*bump]]],
+    )?;
+
+    emit!(EventFeesClaimed {
+      pool: accts.global_state.key(),
+      recipient: recipient_key,
+      amount
+    });
+    Ok(())
+}
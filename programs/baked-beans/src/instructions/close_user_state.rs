@@ -0,0 +1,54 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CloseUserState<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+        constraint = user_state.position_mint == Pubkey::default() @ BeanError::PositionTokenized,
+        close = user,
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+impl<'info> CloseUserState<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.user_state.beans == 0 || max_payout_reached(&self.global_state, &self.user_state),
+            BeanError::UserStateNotClosable
+        );
+        require!(self.user_state.active_locks == 0, BeanError::UserStateNotClosable);
+        Ok(())
+    }
+}
+
+/// Close the user state and return its rent to the user, once it has no beans
+/// or has paid out its max. The user stays listed in their upline's `referrals`,
+/// so a state re-created later doesn't earn that upline a second first deposit bonus
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<CloseUserState>) -> Result<()> {
+    let accts = ctx.accounts;
+    if accts.user_state.total_deposit > 0 {
+      accts.global_state.total_bakers -= 1;
+    }
+
+    emit!(EventUserStateClosed {
+      pool: accts.user_state.pool,
+      user_address: accts.user.key(),
+      upline: accts.user_state.upline
+    });
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*, instructions::bake_beans::process_bake};
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke_signed, system_instruction};
+
+#[derive(Accounts)]
+pub struct CrankBake<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: this should be checked with address in global_state
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(mut, address = global_state.giveaway_pool)]
+    /// CHECK: program controlled giveaway pool
+    pub giveaway_pool: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user_state.user.as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CrankBake<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.user_state.auto_bake_interval > 0, BeanError::AutoBakeDisabled);
+        require!(
+            seconds_since_last_action(&self.user_state) >= self.user_state.auto_bake_interval,
+            BeanError::AutoBakeNotDue
+        );
+        // tips are paid in SOL
+        require!(
+            is_native_pool(&self.global_state) || self.user_state.auto_bake_tip == 0,
+            BeanError::UnsupportedPool
+        );
+        Ok(())
+    }
+}
+
+/// Compound an opted-in user's rewards on their behalf,
+/// tipping the cranker from those rewards if the user configured a tip and it fits their payout limits.
+/// The tip pays the withdrawal fee, tax and giveaway share of an eat but does not restart the tax cycle.
+/// Like `bake_beans` the bake still runs while the pool is paused, only the tip is held back.
+/// The user's lock positions are passed as remaining accounts
+#[access_control(ctx.accounts.validate())]
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CrankBake<'info>>) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let accts = ctx.accounts;
+
+    let mut locks = load_locks(ctx.remaining_accounts, &accts.user_state, accts.user_state.active_locks)?;
+    let mut positions = lock_positions(&locks);
+    let beans_from = accts.user_state.beans;
+    let beans_from_rewards = settle_locks(&accts.user_state, &mut positions, cur_timestamp);
+    let mut tip_beans = bps_from_amount(beans_from_rewards, accts.user_state.auto_bake_tip);
+    let tip_before_fee = beans_to_amount(tip_beans, accts.global_state.price_per_bean);
+    let tax = has_bean_taxed(&accts.global_state.tax_schedule, &accts.user_state);
+    let remaining_payout = max_payout(&accts.global_state, &accts.user_state).saturating_sub(accts.user_state.cycle_payout);
+    let (total_fee, for_giveway, mut sol_tip, payout) = split_eat(tip_before_fee, tax, remaining_payout);
+    // the tip is a payout, skipped while paused or when it would reach the payout cap or pass the user's daily limit
+    if accts.global_state.paused == 1 || sol_tip == 0 || tip_before_fee >= remaining_payout ||
+      sol_tip > withdrawal_allowance(&accts.global_state, &accts.user_state, cur_timestamp) {
+      tip_beans = 0;
+      sol_tip = 0;
+    }
+    process_bake(&mut accts.user_state, beans_from_rewards - tip_beans, cur_timestamp)?;
+    store_locks(&mut locks, &positions, ctx.program_id)?;
+
+    if sol_tip > 0 {
+      add_payout(&mut accts.user_state, payout);
+      count_user_withdrawal(&accts.global_state, &mut accts.user_state, sol_tip, cur_timestamp)?;
+      record_withdrawal(&mut accts.global_state, sol_tip, cur_timestamp)?;
+      release_tvl(&mut accts.global_state, for_giveway);
+      let remained_fee = accrue_fees(&mut accts.fee_schedule, total_fee);
+      accts.global_state.retained_fees += remained_fee;
+
+      let bump = ctx.bumps.get("vault").unwrap();
+      let pool = accts.global_state.key();
+      // send giveaway
+      invoke_signed(
+          &system_instruction::transfer(&accts.vault.key(), &accts.giveaway_pool.key(), for_giveway),
+          &[
+              accts.vault.to_account_info().clone(),
+              accts.giveaway_pool.clone(),
+              accts.system_program.to_account_info().clone(),
+          ],
+          &[&[VAULT_SEED, pool.as_ref(), &[*bump]]],
+      )?;
+
+      // send tip to cranker
+      invoke_signed(
+          &system_instruction::transfer(&accts.vault.key(), &accts.cranker.key(), sol_tip),
+          &[
+              accts.vault.to_account_info().clone(),
+              accts.cranker.to_account_info().clone(),
+              accts.system_program.to_account_info().clone(),
+          ],
+          &[&[VAULT_SEED, pool.as_ref(), &[*bump]]],
+      )?;
+
+      // lamports should be bigger than zero to prevent rent exemption
+      let rent = Rent::default();
+      let required_lamports = rent
+          .minimum_balance(0)
+          .max(1)
+          .saturating_sub(accts.vault.to_account_info().lamports());
+      require!(
+          **accts.vault.lamports.borrow() > required_lamports,
+          BeanError::InsufficientAmount
+      );
+    }
+
+    emit!(EventBaked {
+      pool: accts.global_state.key(),
+      user_address: accts.user_state.user,
+      ref_address: accts.user_state.upline,
+      beans_from,
+      beans_to: accts.user_state.beans
+    });
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct CreatePool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // pools are created by the root pool's authority
+    #[account(
+        seeds = [GLOBAL_STATE_SEED, &ROOT_POOL_ID.to_le_bytes()],
+        bump,
+        has_one = authority @ BeanError::NotAllowedAuthority,
+    )]
+    pub root_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        seeds = [GLOBAL_STATE_SEED, &pool_id.to_le_bytes()],
+        bump,
+        space = 8 + size_of::<GlobalState>(),
+        payer = authority,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+        bump,
+        space = 8 + size_of::<FeeSchedule>() + size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS,
+        payer = authority,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    /// CHECK: this should be set by admin
+    pub dev_account: AccountInfo<'info>,
+    /// CHECK: this should be set by admin
+    pub marketing_account: AccountInfo<'info>,
+    /// CHECK: this should be set by admin
+    pub ceo_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: this should be set by admin
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [GIVEAWAY_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: program controlled giveaway pool
+    pub giveaway_pool: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> CreatePool<'info> {
+    pub fn validate(&self, tax_schedule: &TaxSchedule) -> Result<()> {
+        require!(valid_tax_schedule(tax_schedule), BeanError::InvalidTaxSchedule);
+        Ok(())
+    }
+}
+
+/// Open another SOL pool from the same deployment, with its own authority,
+/// tax schedule, vault and users. `migrated_from` names the pool this one succeeds,
+/// default for none, see `migrate_vault`
+#[access_control(ctx.accounts.validate(&tax_schedule))]
+pub fn handle(
+    ctx: Context<CreatePool>,
+    pool_id: u64,
+    new_authority: Pubkey,
+    tax_schedule: TaxSchedule,
+    migrated_from: Pubkey,
+) -> Result<()> {
+    require!(new_authority.ne(&Pubkey::default()), BeanError::ZeroAddressDetected);
+
+    let accts = ctx.accounts;
+    accts.global_state.is_initialized = 1;
+    accts.global_state.authority = new_authority;
+    accts.global_state.pool_id = pool_id;
+    accts.global_state.mint = NATIVE_MINT;
+    accts.global_state.price_per_bean = SOL_PER_BEAN;
+    accts.global_state.vault = accts.vault.key();
+    accts.global_state.giveaway_pool = accts.giveaway_pool.key();
+    accts.global_state.tax_schedule = tax_schedule;
+    accts.global_state.max_payout_multiplier = DEFAULT_MAX_PAYOUT_MULTIPLIER;
+    accts.global_state.migrated_from = migrated_from;
+
+    accts.fee_schedule.bump = *ctx.bumps.get("fee_schedule").unwrap();
+    accts.fee_schedule.pool = accts.global_state.key();
+    accts.fee_schedule.recipients = default_fee_recipients(
+        accts.dev_account.key(),
+        accts.marketing_account.key(),
+        accts.ceo_account.key(),
+    );
+
+    for pda in [&accts.vault, &accts.giveaway_pool] {
+        fund_rent_exempt(
+            &accts.authority.to_account_info(),
+            pda,
+            &accts.system_program.to_account_info(),
+        )?;
+    }
+
+    emit!(EventPoolCreated {
+      pool: accts.global_state.key(),
+      pool_id,
+      mint: NATIVE_MINT,
+      authority: new_authority
+    });
+    Ok(())
+}
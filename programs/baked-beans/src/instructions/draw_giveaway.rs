@@ -0,0 +1,122 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+use solana_program::{
+    hash::{hash, hashv},
+    program::invoke_signed,
+    system_instruction,
+};
+
+#[derive(Accounts)]
+pub struct DrawGiveaway<'info> {
+    // the authority revealing its secret, or anyone once the grace period passed
+    pub caller: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = is_native_pool(&global_state) @ BeanError::UnsupportedPool,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [GIVEAWAY_ROUND_SEED, global_state.key().as_ref(), &giveaway_round.round_id.to_le_bytes()],
+        bump = giveaway_round.bump,
+    )]
+    pub giveaway_round: Account<'info, GiveawayRound>,
+
+    #[account(
+        mut,
+        seeds = [GIVEAWAY_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: program controlled giveaway pool
+    pub giveaway_pool: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: checked against the drawn ticket
+    pub winner: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DrawGiveaway<'info> {
+    pub fn validate(&self, secret: &[u8; 32]) -> Result<()> {
+        validate_draw(&self.giveaway_round, secret)
+    }
+}
+
+/// Reveal the authority secret, draw the winning ticket and pay out the pool.
+/// If the authority doesn't draw within the grace period after the reveal deadline,
+/// anyone can draw from the participants' secrets alone.
+/// A round without tickets leaves the pool for the next one
+#[access_control(ctx.accounts.validate(&secret))]
+pub fn handle(ctx: Context<DrawGiveaway>, secret: [u8; 32]) -> Result<()> {
+    let accts = ctx.accounts;
+    let round = &mut accts.giveaway_round;
+
+    if let Some(winner) = draw_round(&mut accts.global_state, round, &secret) {
+      require!(winner.eq(accts.winner.key), BeanError::IncorrectGiveawayWinner);
+
+      // keep the pool rent exempt
+      let rent = Rent::default();
+      let prize = accts.giveaway_pool
+        .lamports()
+        .saturating_sub(rent.minimum_balance(0).max(1));
+      round.winner = winner;
+      round.prize = prize;
+
+      let bump = ctx.bumps.get("giveaway_pool").unwrap();
+      let pool = accts.global_state.key();
+      invoke_signed(
+          &system_instruction::transfer(&accts.giveaway_pool.key(), &winner, prize),
+          &[
+              accts.giveaway_pool.clone(),
+              accts.winner.clone(),
+              accts.system_program.to_account_info().clone(),
+          ],
+          &[&[GIVEAWAY_SEED, pool.as_ref(), &[*bump]]],
+      )?;
+    }
+
+    emit!(EventGiveawayDrawn {
+      pool: accts.global_state.key(),
+      round_id: round.round_id,
+      seed: round.seed,
+      total_tickets: revealed_tickets(round),
+      winner: round.winner,
+      prize: round.prize
+    });
+    Ok(())
+}
+
+/// Shared by `draw_giveaway` and `draw_giveaway_token`
+pub fn validate_draw(round: &GiveawayRound, secret: &[u8; 32]) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    require!(round.is_drawn == 0, BeanError::GiveawayAlreadyDrawn);
+    require!(
+        cur_timestamp > round.reveal_deadline,
+        BeanError::GiveawayRoundOpen
+    );
+    require!(
+        authority_secret_valid(round, secret) || cur_timestamp > round.reveal_deadline + GIVEAWAY_DRAW_GRACE_PERIOD,
+        BeanError::InvalidGiveawaySecret
+    );
+    Ok(())
+}
+
+pub fn authority_secret_valid(round: &GiveawayRound, secret: &[u8; 32]) -> bool {
+    hash(secret).to_bytes() == round.authority_commitment
+}
+
+/// Mix the authority secret into the seed, unless it is not the committed one,
+/// and mark the round drawn. Returns the winner if any
+pub fn draw_round(global_state: &mut GlobalState, round: &mut GiveawayRound, secret: &[u8; 32]) -> Option<Pubkey> {
+    if authority_secret_valid(round, secret) {
+      round.seed = hashv(&[&round.seed, secret]).to_bytes();
+    }
+    round.is_drawn = 1;
+    global_state.giveaway_drawn_round = round.round_id;
+    pick_giveaway_winner(round)
+}
@@ -0,0 +1,88 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*, instructions::draw_giveaway::{draw_round, validate_draw}};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct DrawGiveawayToken<'info> {
+    // the authority revealing its secret, or anyone once the grace period passed
+    pub caller: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = !is_native_pool(&global_state) @ BeanError::UnsupportedPool,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [GIVEAWAY_ROUND_SEED, global_state.key().as_ref(), &giveaway_round.round_id.to_le_bytes()],
+        bump = giveaway_round.bump,
+    )]
+    pub giveaway_round: Account<'info, GiveawayRound>,
+
+    #[account(
+        seeds = [GIVEAWAY_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: owner of the giveaway token account
+    pub giveaway_authority: AccountInfo<'info>,
+
+    #[account(mut, address = global_state.giveaway_pool)]
+    /// CHECK: the pool's giveaway token account
+    pub giveaway_pool: AccountInfo<'info>,
+
+    #[account(mut)]
+    /// CHECK: checked to be a token account of the drawn winner
+    pub winner: AccountInfo<'info>,
+
+    #[account(address = spl_token::ID)]
+    /// CHECK: spl token program
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> DrawGiveawayToken<'info> {
+    pub fn validate(&self, secret: &[u8; 32]) -> Result<()> {
+        validate_draw(&self.giveaway_round, secret)
+    }
+}
+
+/// `draw_giveaway` for token pools, the winner is paid the whole giveaway token balance
+#[access_control(ctx.accounts.validate(&secret))]
+pub fn handle(ctx: Context<DrawGiveawayToken>, secret: [u8; 32]) -> Result<()> {
+    let accts = ctx.accounts;
+    let round = &mut accts.giveaway_round;
+
+    if let Some(winner) = draw_round(&mut accts.global_state, round, &secret) {
+      let winner_account = load_token_account(&accts.winner)?;
+      require!(
+        winner_account.owner.eq(&winner) && winner_account.mint.eq(&accts.global_state.mint),
+        BeanError::IncorrectGiveawayWinner
+      );
+
+      let prize = load_token_account(&accts.giveaway_pool)?.amount;
+      round.winner = winner;
+      round.prize = prize;
+
+      let bump = ctx.bumps.get("giveaway_authority").unwrap();
+      let pool = accts.global_state.key();
+      transfer_tokens(
+        &accts.token_program,
+        &accts.giveaway_pool,
+        &accts.winner,
+        &accts.giveaway_authority,
+        prize,
+        &[&[GIVEAWAY_SEED, pool.as_ref(), &[*bump]]],
+      )?;
+    }
+
+    emit!(EventGiveawayDrawn {
+      pool: accts.global_state.key(),
+      round_id: round.round_id,
+      seed: round.seed,
+      total_tickets: revealed_tickets(round),
+      winner: round.winner,
+      prize: round.prize
+    });
+    Ok(())
+}
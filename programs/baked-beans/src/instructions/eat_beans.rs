@@ -3,161 +3,180 @@ use anchor_lang::prelude::*;
 use solana_program::{program::invoke_signed, system_instruction};
 #[derive(Accounts)]
 pub struct EatBeans<'info> {
+    // the user or their delegate
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(mut)]
+    /// CHECK: the owner's wallet, or a destination allowed by their whitelist
+    pub recipient: AccountInfo<'info>,
+
     #[account(
       mut,
-      seeds = [GLOBAL_STATE_SEED],
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
       bump,
+      constraint = is_native_pool(&global_state) @ BeanError::UnsupportedPool,
+      constraint = global_state.paused == 0 @ BeanError::PoolPaused,
     )]
     pub global_state: Account<'info, GlobalState>,
 
     #[account(
         mut,
-        seeds = [VAULT_SEED],
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
         bump
     )]
     /// CHECK: this should be checked with address in global_state
     pub vault: AccountInfo<'info>,
 
-    #[account(mut, address = global_state.dev_account)]
-    /// CHECK: this should be set by admin
-    pub dev_account: AccountInfo<'info>,
-
-    #[account(mut, address = global_state.marketing_account)]
-    /// CHECK: this should be set by admin
-    pub marketing_account: AccountInfo<'info>,
-
-    #[account(mut, address = global_state.ceo_account)]
-    /// CHECK: this should be set by admin
-    pub ceo_account: AccountInfo<'info>,
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
 
-    #[account(mut, address = global_state.giveaway_account)]
-    /// CHECK: this should be set by admin
-    pub giveaway_account: AccountInfo<'info>,
+    #[account(mut, address = global_state.giveaway_pool)]
+    /// CHECK: program controlled giveaway pool
+    pub giveaway_pool: AccountInfo<'info>,
 
     #[account(
         mut,
-        seeds = [USER_STATE_SEED, user.key().as_ref()],
-        bump,
-        has_one = user
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user_state.user.as_ref()],
+        bump = user_state.bump,
     )]
     pub user_state: Account<'info, UserState>,
 
+    /// CHECK: holder's token account of the position NFT, ignored for positions without one
+    pub position_token: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> EatBeans<'info> {
     pub fn validate(&self) -> Result<()> {
+        let owner = position_owner(&self.user_state, &self.position_token)?;
+        require!(can_eat(&self.user_state, owner, self.user.key()), BeanError::NotAllowedDelegate);
+        require!(
+            recipient_allowed(&self.user_state, owner, self.user.key(), self.recipient.key()),
+            BeanError::RecipientNotAllowed
+        );
         Ok(())
     }
+
+    /// Pay out `beans_before_fee` of rewards net of fees and taxes,
+    /// shared by `eat_beans` and `harvest`. Returns the lamports sent to the recipient
+    pub fn process_eat(
+        &mut self,
+        vault_bump: u8,
+        beans_before_fee: u64,
+        cur_timestamp: u64,
+    ) -> Result<u64> {
+        let (for_giveway, sol_to_eat) = settle_eat(
+          &mut self.global_state,
+          &mut self.fee_schedule,
+          &mut self.user_state,
+          beans_before_fee,
+          cur_timestamp,
+        )?;
+        let pool = self.global_state.key();
+
+        // send giveaway
+        invoke_signed(
+          &system_instruction::transfer(&self.vault.key(), &self.giveaway_pool.key(), for_giveway),
+          &[
+              self.vault.to_account_info().clone(),
+              self.giveaway_pool.clone(),
+              self.system_program.to_account_info().clone(),
+          ],
+          &[&[VAULT_SEED, pool.as_ref(), &[vault_bump]]],
+        )?;
+
+        // send to user
+        invoke_signed(
+            &system_instruction::transfer(&self.vault.key(), &self.recipient.key(), sol_to_eat),
+            &[
+                self.vault.to_account_info().clone(),
+                self.recipient.clone(),
+                self.system_program.to_account_info().clone(),
+            ],
+            &[&[VAULT_SEED, pool.as_ref(), &[vault_bump]]],
+        )?;
+
+        // lamports should be bigger than zero to prevent rent exemption
+        let rent = Rent::default();
+        let required_lamports = rent
+            .minimum_balance(0)
+            .max(1)
+            .saturating_sub(self.vault.to_account_info().lamports());
+        require!(
+            **self.vault.lamports.borrow() > required_lamports,
+            BeanError::InsufficientAmount
+        );
+
+        Ok(sol_to_eat)
+    }
 }
 
+/// Withdraw `amount` beans of pending rewards, or all of them when `None`.
+/// The rest stays accrued, and any eat, partial or not, restarts the tax cycle.
+/// Rewards of lock positions, passed as remaining accounts, can only be eaten once unlocked
 #[access_control(ctx.accounts.validate())]
-pub fn handle(ctx: Context<EatBeans>) -> Result<()> {
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, EatBeans<'info>>, amount: Option<u64>) -> Result<()> {
     let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let bump = *ctx.bumps.get("vault").unwrap();
     let accts = ctx.accounts;
 
-    require!(accts.user_state.total_deposit > 0, BeanError::InvalidAction);
-    require!(max_payout_reached(&accts.user_state) == false, BeanError::MaxPayoutReached);
-
-    let beans_before_fee = rewarded_beans(&accts.user_state);
-    let beans_in_sol_before_fee = beans_to_sol(beans_before_fee);
-    let total_sol_fee = percent_from_amount(beans_in_sol_before_fee, WITHDRAWAL_FEE);
-    
-    let mut sol_to_eat = beans_in_sol_before_fee - total_sol_fee;
-    let for_giveway = calc_giveaway_amount(&accts.user_state, sol_to_eat);
-    sol_to_eat = add_withdrawal_taxes(&accts.user_state, sol_to_eat);
-
-    if beans_in_sol_before_fee + accts.user_state.total_payout >= max_payout(&accts.user_state) {
-      sol_to_eat = max_payout(&accts.user_state) - accts.user_state.total_payout;
-      accts.user_state.total_payout = max_payout(&accts.user_state);
-    } else {
-      let after_tax = add_withdrawal_taxes(
-        &accts.user_state,
-        beans_in_sol_before_fee
-      );
-      accts.user_state.total_payout = accts.user_state.total_payout + after_tax;
+    let mut locks = load_locks(ctx.remaining_accounts, &accts.user_state, accts.user_state.active_locks)?;
+    let mut positions = lock_positions(&locks);
+    let pending = settle_locks(&accts.user_state, &mut positions, cur_timestamp);
+    let beans_before_fee = amount.unwrap_or(pending);
+    if amount.is_some() {
+      require!(beans_before_fee > 0, BeanError::InsufficientAmount);
+      require!(beans_before_fee <= pending, BeanError::ExceedsPendingRewards);
     }
 
-    accts.user_state.ate_at = cur_timestamp;
-    accts.user_state.baked_at = cur_timestamp;
-    
-    let bump = ctx.bumps.get("vault").unwrap();
-    // send giveaway
-    invoke_signed(
-      &system_instruction::transfer(&accts.vault.key(), &accts.giveaway_account.key(), for_giveway),
-      &[
-          accts.vault.to_account_info().clone(),
-          accts.giveaway_account.clone(),
-          accts.system_program.to_account_info().clone(),
-      ],
-      &[&[VAULT_SEED, &[*bump]]],
-    )?;
-    
-    // fee distribution
-    let dev_fee = percent_from_amount(total_sol_fee, DEV_FEE);
-    let market_fee = percent_from_amount(total_sol_fee, MARKETING_FEE);
-    let ceo_fee = percent_from_amount(total_sol_fee, CEO_FEE);
-
-    // send dev_fee
-    invoke_signed(
-        &system_instruction::transfer(&accts.vault.key(), &accts.dev_account.key(), dev_fee),
-        &[
-            accts.vault.to_account_info().clone(),
-            accts.dev_account.clone(),
-            accts.system_program.to_account_info().clone(),
-        ],
-        &[&[VAULT_SEED, &[*bump]]],
-    )?;
-    // send marketing_fee
-    invoke_signed(
-          &system_instruction::transfer(&accts.vault.key(), &accts.marketing_account.key(), market_fee),
-          &[
-              accts.vault.to_account_info().clone(),
-              accts.marketing_account.clone(),
-              accts.system_program.to_account_info().clone(),
-          ],
-          &[&[VAULT_SEED, &[*bump]]],
-      )?;
-    // send ceo_fee
-    invoke_signed(
-        &system_instruction::transfer(&accts.vault.key(), &accts.ceo_account.key(), ceo_fee),
-        &[
-            accts.vault.to_account_info().clone(),
-            accts.ceo_account.clone(),
-            accts.system_program.to_account_info().clone(),
-        ],
-        &[&[VAULT_SEED, &[*bump]]],
-    )?;
-
-    // send to user
-    invoke_signed(
-        &system_instruction::transfer(&accts.vault.key(), &accts.user.key(), sol_to_eat),
-        &[
-            accts.vault.to_account_info().clone(),
-            accts.user.to_account_info().clone(),
-            accts.system_program.to_account_info().clone(),
-        ],
-        &[&[VAULT_SEED, &[*bump]]],
-    )?;
-
-    // lamports should be bigger than zero to prevent rent exemption
-    let rent = Rent::default();
-    let required_lamports = rent
-        .minimum_balance(0)
-        .max(1)
-        .saturating_sub(accts.vault.to_account_info().lamports());
-    require!(
-        **accts.vault.lamports.borrow() > required_lamports,
-        BeanError::InsufficientAmount
-    );
+    let sol_to_eat = accts.process_eat(bump, beans_before_fee, cur_timestamp)?;
+    accts.user_state.accrued_beans = pending - beans_before_fee;
+    store_locks(&mut locks, &positions, ctx.program_id)?;
 
     emit!(EventAte {
-      user_address: accts.user.key(),
+      pool: accts.global_state.key(),
+      user_address: accts.user_state.user,
+      recipient: accts.recipient.key(),
       sol_to_eat,
       beans_before_fee
     });
     Ok(())
 }
+
+/// Settle an eat of `beans_before_fee` against the user's payout and tax timer and accrue its fee.
+/// Returns the giveaway share and the amount to send, in the pool's units, for the caller to transfer
+pub fn settle_eat(
+    global_state: &mut GlobalState,
+    fee_schedule: &mut FeeSchedule,
+    user_state: &mut UserState,
+    beans_before_fee: u64,
+    cur_timestamp: u64,
+) -> Result<(u64, u64)> {
+    require!(user_state.total_deposit > 0, BeanError::InvalidAction);
+    require!(max_payout_reached(global_state, user_state) == false, BeanError::MaxPayoutReached);
+
+    let amount_before_fee = beans_to_amount(beans_before_fee, global_state.price_per_bean);
+    let tax = has_bean_taxed(&global_state.tax_schedule, user_state);
+    let remaining_payout = max_payout(global_state, user_state) - user_state.cycle_payout;
+    let (total_fee, for_giveway, to_eat, payout) = split_eat(amount_before_fee, tax, remaining_payout);
+    add_payout(user_state, payout);
+
+    record_withdrawal(global_state, to_eat, cur_timestamp)?;
+    release_tvl(global_state, for_giveway);
+    record_user_withdrawal(global_state, user_state, to_eat, cur_timestamp)?;
+
+    user_state.ate_at = cur_timestamp;
+    user_state.baked_at = cur_timestamp;
+
+    // fees accrue to the fee schedule, the unassigned share and rounding dust stay in the vault
+    let remained_fee = accrue_fees(fee_schedule, total_fee);
+    global_state.retained_fees += remained_fee;
+
+    Ok((for_giveway, to_eat))
+}
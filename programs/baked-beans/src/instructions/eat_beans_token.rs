@@ -0,0 +1,128 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*, instructions::eat_beans::settle_eat};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct EatBeansToken<'info> {
+    // the user or their delegate
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    /// CHECK: a token account of the pool's mint owned by the position owner or a whitelisted recipient
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = !is_native_pool(&global_state) @ BeanError::UnsupportedPool,
+      constraint = global_state.paused == 0 @ BeanError::PoolPaused,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: owner of the token vault
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(mut, address = global_state.vault)]
+    /// CHECK: the pool's token vault
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(mut, address = global_state.giveaway_pool)]
+    /// CHECK: the pool's giveaway token account
+    pub giveaway_pool: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user_state.user.as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    /// CHECK: holder's token account of the position NFT, ignored for positions without one
+    pub position_token: AccountInfo<'info>,
+
+    #[account(address = spl_token::ID)]
+    /// CHECK: spl token program
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'info> EatBeansToken<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let owner = position_owner(&self.user_state, &self.position_token)?;
+        require!(can_eat(&self.user_state, owner, self.user.key()), BeanError::NotAllowedDelegate);
+        let recipient = load_token_account(&self.recipient)?;
+        require!(recipient.mint.eq(&self.global_state.mint), BeanError::InvalidTokenAccount);
+        require!(
+            recipient_allowed(&self.user_state, owner, self.user.key(), recipient.owner),
+            BeanError::RecipientNotAllowed
+        );
+        Ok(())
+    }
+}
+
+/// `eat_beans` for token pools, proceeds are paid in the pool's mint
+#[access_control(ctx.accounts.validate())]
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, EatBeansToken<'info>>, amount: Option<u64>) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let bump = *ctx.bumps.get("vault_authority").unwrap();
+    let accts = ctx.accounts;
+
+    let mut locks = load_locks(ctx.remaining_accounts, &accts.user_state, accts.user_state.active_locks)?;
+    let mut positions = lock_positions(&locks);
+    let pending = settle_locks(&accts.user_state, &mut positions, cur_timestamp);
+    let beans_before_fee = amount.unwrap_or(pending);
+    if amount.is_some() {
+      require!(beans_before_fee > 0, BeanError::InsufficientAmount);
+      require!(beans_before_fee <= pending, BeanError::ExceedsPendingRewards);
+    }
+
+    let (for_giveway, amount_to_eat) = settle_eat(
+      &mut accts.global_state,
+      &mut accts.fee_schedule,
+      &mut accts.user_state,
+      beans_before_fee,
+      cur_timestamp,
+    )?;
+    accts.user_state.accrued_beans = pending - beans_before_fee;
+    store_locks(&mut locks, &positions, ctx.program_id)?;
+
+    let pool = accts.global_state.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, pool.as_ref(), &[bump]]];
+    // send giveaway
+    transfer_tokens(
+      &accts.token_program,
+      &accts.vault,
+      &accts.giveaway_pool,
+      &accts.vault_authority,
+      for_giveway,
+      signer_seeds,
+    )?;
+    // send to user
+    transfer_tokens(
+      &accts.token_program,
+      &accts.vault,
+      &accts.recipient,
+      &accts.vault_authority,
+      amount_to_eat,
+      signer_seeds,
+    )?;
+
+    emit!(EventAte {
+      pool,
+      user_address: accts.user_state.user,
+      recipient: accts.recipient.key(),
+      sol_to_eat: amount_to_eat,
+      beans_before_fee
+    });
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct EnterGiveaway<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [USER_STATE_SEED, user_state.pool.as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+        mut,
+        seeds = [GIVEAWAY_ROUND_SEED, user_state.pool.as_ref(), &giveaway_round.round_id.to_le_bytes()],
+        bump = giveaway_round.bump,
+    )]
+    pub giveaway_round: Account<'info, GiveawayRound>,
+}
+
+impl<'info> EnterGiveaway<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(cur_timestamp <= self.giveaway_round.end_time, BeanError::GiveawayRoundClosed);
+        require!(
+            self.user_state.giveaway_round == self.giveaway_round.round_id &&
+            self.user_state.giveaway_deposit >= sol_to_beans(GIVEAWAY_TICKET_DEPOSIT),
+            BeanError::NotEligibleForGiveaway
+        );
+        Ok(())
+    }
+}
+
+/// Enter the round with one ticket per GIVEAWAY_TICKET_DEPOSIT worth of beans bought during it.
+/// `commitment` is the hash of the user's key and a secret revealed after entries close.
+/// Entering again refreshes the tickets and keeps the first commitment
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<EnterGiveaway>, commitment: [u8; 32]) -> Result<()> {
+    let accts = ctx.accounts;
+    let user_key = accts.user.key();
+    let tickets = accts.user_state.giveaway_deposit / sol_to_beans(GIVEAWAY_TICKET_DEPOSIT);
+    let round: &mut GiveawayRound = &mut accts.giveaway_round;
+
+    match round.participants.iter_mut().find(|participant| participant.user.eq(&user_key)) {
+      Some(participant) => {
+        round.total_tickets = round.total_tickets - participant.tickets + tickets;
+        participant.tickets = tickets;
+      }
+      None => {
+        require!(round.participants.len() < MAX_GIVEAWAY_PARTICIPANTS, BeanError::GiveawayFull);
+        round.total_tickets += tickets;
+        round.participants.push(GiveawayTicket {
+          user: user_key,
+          tickets,
+          commitment,
+          revealed: 0
+        });
+      }
+    }
+
+    emit!(EventGiveawayEntered {
+      pool: round.pool,
+      round_id: round.round_id,
+      user_address: user_key,
+      tickets
+    });
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ExecuteAdminAction<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = is_admin(&global_state, admin.key()) @ BeanError::NotAllowedAuthority,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(
+        mut,
+        seeds = [QUEUED_ACTION_SEED, global_state.key().as_ref(), &queued_action.action_id.to_le_bytes()],
+        bump = queued_action.bump,
+        close = proposer,
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(mut, address = queued_action.proposer)]
+    /// CHECK: refunded the proposal's rent
+    pub proposer: AccountInfo<'info>,
+}
+
+impl<'info> ExecuteAdminAction<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(
+            !matches!(self.queued_action.action, AdminAction::MigrateVault { .. }),
+            BeanError::InvalidAction
+        );
+        require!(cur_timestamp >= self.queued_action.eta, BeanError::ActionNotReady);
+        require!(
+            approval_count(&self.global_state, &self.queued_action) >= self.global_state.admin_threshold,
+            BeanError::NotEnoughApprovals
+        );
+        check_admin_action(&self.global_state, &self.fee_schedule, &self.queued_action.action)?;
+        Ok(())
+    }
+}
+
+/// Apply a queued admin action whose delay has passed and that enough current admins approved
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+    let accts = ctx.accounts;
+    let action = accts.queued_action.action.clone();
+    let pool = accts.global_state.key();
+    apply_admin_action(pool, &mut accts.global_state, &mut accts.fee_schedule, accts.admin.key(), action.clone());
+
+    emit!(EventAdminActionExecuted {
+      pool,
+      action_id: accts.queued_action.action_id,
+      action
+    });
+    Ok(())
+}
+
+pub fn apply_admin_action(
+    pool: Pubkey,
+    global_state: &mut GlobalState,
+    fee_schedule: &mut FeeSchedule,
+    authority: Pubkey,
+    action: AdminAction,
+) {
+    match action {
+      AdminAction::SetTaxSchedule { tax_schedule } => {
+        global_state.tax_schedule = tax_schedule;
+        emit!(EventTaxScheduleSet { pool, authority, tax_schedule });
+      }
+      AdminAction::SetMaxPayoutMultiplier { max_payout_multiplier } => {
+        global_state.max_payout_multiplier = max_payout_multiplier;
+        emit!(EventMaxPayoutMultiplierSet { pool, authority, max_payout_multiplier });
+      }
+      AdminAction::SetPoolLimits { limits } => {
+        if limits.epoch_length != global_state.limits.epoch_length {
+          global_state.epoch_start = 0;
+        }
+        global_state.limits = limits;
+        emit!(EventPoolLimitsSet { pool, authority, limits });
+      }
+      AdminAction::SetLaunchSchedule { launch_time, whitelist_end, whitelist_root } => {
+        global_state.launch_time = launch_time;
+        global_state.whitelist_end = whitelist_end;
+        global_state.whitelist_root = whitelist_root;
+        emit!(EventLaunchScheduleSet { pool, authority, launch_time, whitelist_end, whitelist_root });
+      }
+      AdminAction::SetUserInitPolicy { restrict_user_init, relayers } => {
+        global_state.restrict_user_init = restrict_user_init;
+        global_state.relayers = [Pubkey::default(); MAX_RELAYERS];
+        global_state.relayers[..relayers.len()].copy_from_slice(&relayers);
+        emit!(EventUserInitPolicySet { pool, authority, restrict_user_init, relayers });
+      }
+      AdminAction::AddFeeRecipient { account, weight } => {
+        fee_schedule.recipients.push(FeeRecipient { account, weight, accrued: 0 });
+        emit!(EventFeeScheduleUpdated { pool, authority, recipients: fee_schedule.recipients.clone() });
+      }
+      AdminAction::RemoveFeeRecipient { account } => {
+        fee_schedule.recipients.retain(|recipient| recipient.account.ne(&account));
+        emit!(EventFeeScheduleUpdated { pool, authority, recipients: fee_schedule.recipients.clone() });
+      }
+      AdminAction::SetFeeRecipientWeight { account, weight } => {
+        for recipient in fee_schedule.recipients.iter_mut() {
+          if recipient.account.eq(&account) {
+            recipient.weight = weight;
+          }
+        }
+        emit!(EventFeeScheduleUpdated { pool, authority, recipients: fee_schedule.recipients.clone() });
+      }
+      AdminAction::SetAdminDelay { admin_delay } => {
+        global_state.admin_delay = admin_delay;
+        emit!(EventAdminDelaySet { pool, authority, admin_delay });
+      }
+      AdminAction::SetAdmins { admins, admin_threshold } => {
+        global_state.admin_threshold = admin_threshold;
+        global_state.admins = [Pubkey::default(); MAX_ADMINS];
+        global_state.admins[..admins.len()].copy_from_slice(&admins);
+        emit!(EventAdminsSet { pool, authority, admins, admin_threshold });
+      }
+      AdminAction::Reconfigure { new_authority, dev_account, marketing_account, ceo_account } => {
+        global_state.authority = new_authority;
+        fee_schedule.recipients = default_fee_recipients(dev_account, marketing_account, ceo_account);
+        emit!(EventReconfigured { pool, authority, new_authority, recipients: fee_schedule.recipients.clone() });
+      }
+      // needs the vault accounts, see migrate_vault
+      AdminAction::MigrateVault { .. } => {}
+      AdminAction::Unpause => {
+        global_state.paused = 0;
+        emit!(EventPausedSet { pool, authority, paused: 0 });
+      }
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{constants::*, error::*, utils::*, events::*, instructions::{bake_beans::process_bake, eat_beans::EatBeans}};
+use anchor_lang::prelude::*;
+
+/// Split pending rewards in one go: `compound_bps` basis points are baked
+/// and the rest is eaten, with the same rules as `bake_beans` and `eat_beans`
+#[access_control(ctx.accounts.validate())]
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, EatBeans<'info>>, compound_bps: u64) -> Result<()> {
+    require!(compound_bps <= BPS_DENOMINATOR, BeanError::InvalidAction);
+
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let bump = *ctx.bumps.get("vault").unwrap();
+    let accts = ctx.accounts;
+
+    let beans_from = accts.user_state.beans;
+    let mut locks = load_locks(ctx.remaining_accounts, &accts.user_state, accts.user_state.active_locks)?;
+    let mut positions = lock_positions(&locks);
+    let pending = settle_locks(&accts.user_state, &mut positions, cur_timestamp);
+    let beans_baked = bps_from_amount(pending, compound_bps);
+    let beans_eaten = pending - beans_baked;
+
+    // eat first so the tax is computed from the previous eat
+    let mut sol_to_eat = 0;
+    if beans_eaten > 0 {
+      sol_to_eat = accts.process_eat(bump, beans_eaten, cur_timestamp)?;
+    }
+    if beans_baked > 0 {
+      process_bake(&mut accts.user_state, beans_baked, cur_timestamp)?;
+    }
+    accts.user_state.accrued_beans = 0;
+    store_locks(&mut locks, &positions, ctx.program_id)?;
+
+    emit!(EventHarvested {
+      pool: accts.global_state.key(),
+      user_address: accts.user_state.user,
+      recipient: accts.recipient.key(),
+      beans_baked,
+      beans_eaten,
+      sol_to_eat,
+      beans_from,
+      beans_to: accts.user_state.beans
+    });
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::{constants::*, error::*, states::*};
+use crate::{constants::*, error::*, states::*, utils::*};
 use anchor_lang::prelude::*;
 
 use std::mem::size_of;
@@ -8,12 +8,18 @@ use std::mem::size_of;
 pub struct InitUserState<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
-    
+
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
     #[account(
         init,
         payer = payer,
-        space = 8 + size_of::<UserState>() + 32 * 100,
-        seeds = [USER_STATE_SEED, user_key.as_ref()],
+        space = 8 + size_of::<UserState>() + 32 * 100 + 32 * MAX_WHITELISTED_RECIPIENTS,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user_key.as_ref()],
         bump
     )]
     pub user_state: Account<'info, UserState>,
@@ -23,18 +29,23 @@ pub struct InitUserState<'info> {
 }
 
 impl<'info> InitUserState<'info> {
-    fn validate(&self) -> Result<()> {
+    fn validate(&self, user_key: Pubkey) -> Result<()> {
+        require!(
+            payer_allowed(&self.global_state, self.payer.key(), user_key),
+            BeanError::PayerNotAllowed
+        );
         Ok(())
     }
 }
 
-#[access_control(ctx.accounts.validate())]
+#[access_control(ctx.accounts.validate(user_key))]
 pub fn handle(ctx: Context<InitUserState>, user_key: Pubkey) -> Result<()> {
     require!(user_key.ne(&Pubkey::default()), BeanError::ZeroAddressDetected);
     // let current_time = Clock::get()?.unix_timestamp as u64;
 
     let accts = ctx.accounts;
     accts.user_state.user = user_key;
+    accts.user_state.pool = accts.global_state.key();
     accts.user_state.bump = *ctx.bumps.get("user_state").unwrap();
     Ok(())
 }
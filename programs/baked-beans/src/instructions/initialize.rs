@@ -1,87 +1,96 @@
-use crate::{constants::*, error::*, states::*};
+use crate::{constants::*, error::*, states::*, utils::*, program::BakedBeans};
 use anchor_lang::prelude::*;
-use solana_program::{program::invoke, system_instruction};
 use std::mem::size_of;
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
+    // the program's upgrade authority, so nobody can front-run the deployment
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, BakedBeans>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ BeanError::NotAllowedAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
     #[account(
         init,
-        seeds = [GLOBAL_STATE_SEED],
+        seeds = [GLOBAL_STATE_SEED, &ROOT_POOL_ID.to_le_bytes()],
         bump,
         space = 8 + size_of::<GlobalState>(),
         payer = authority,
     )]
     pub global_state: Account<'info, GlobalState>,
 
+    #[account(
+        init,
+        seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+        bump,
+        space = 8 + size_of::<FeeSchedule>() + size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS,
+        payer = authority,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
     /// CHECK: this should be set by admin
     pub dev_account: AccountInfo<'info>,
     /// CHECK: this should be set by admin
     pub marketing_account: AccountInfo<'info>,
     /// CHECK: this should be set by admin
     pub ceo_account: AccountInfo<'info>,
-    /// CHECK: this should be set by admin
-    pub giveaway_account: AccountInfo<'info>,
 
     #[account(
         mut,
-        seeds = [VAULT_SEED],
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
         bump
     )]
     /// CHECK: this should be set by admin
     pub vault: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [GIVEAWAY_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: program controlled giveaway pool
+    pub giveaway_pool: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-impl<'info> Initialize<'info> {
-    pub fn validate(&self) -> Result<()> {
-        if self.global_state.is_initialized == 1 {
-            require!(
-                self.global_state.authority.eq(&self.authority.key()),
-                BeanError::NotAllowedAuthority
-            )
-        }
-        Ok(())
-    }
-}
-
 /// Initialize Staking Program for the first time
-/// to init global state with some data for validation
-///
-#[access_control(ctx.accounts.validate())]
+/// to init global state with some data for validation.
+/// Runs once, later changes go through reconfigure
 pub fn handle(ctx: Context<Initialize>, new_authority: Pubkey) -> Result<()> {
     let accts = ctx.accounts;
     accts.global_state.is_initialized = 1;
     accts.global_state.authority = new_authority;
+    accts.global_state.pool_id = ROOT_POOL_ID;
+    accts.global_state.mint = NATIVE_MINT;
+    accts.global_state.price_per_bean = SOL_PER_BEAN;
     accts.global_state.vault = accts.vault.key();
-    accts.global_state.dev_account = accts.dev_account.key();
-    accts.global_state.marketing_account = accts.marketing_account.key();
-    accts.global_state.ceo_account = accts.ceo_account.key();
-    accts.global_state.giveaway_account = accts.giveaway_account.key();
+    accts.global_state.giveaway_pool = accts.giveaway_pool.key();
+    accts.global_state.tax_schedule = default_tax_schedule();
+    accts.global_state.max_payout_multiplier = DEFAULT_MAX_PAYOUT_MULTIPLIER;
 
-    let rent = Rent::default();
-    let required_lamports = rent
-        .minimum_balance(0)
-        .max(1)
-        .saturating_sub(accts.vault.to_account_info().lamports());
-        
-    invoke(
-        &system_instruction::transfer(
-            &accts.authority.key(),
-            &accts.vault.key(),
-            required_lamports,
-        ),
-        &[
-            accts.authority.to_account_info().clone(),
-            accts.vault.clone(),
-            accts.system_program.to_account_info().clone(),
-        ],
-    )?;
+    accts.fee_schedule.bump = *ctx.bumps.get("fee_schedule").unwrap();
+    accts.fee_schedule.pool = accts.global_state.key();
+    accts.fee_schedule.recipients = default_fee_recipients(
+        accts.dev_account.key(),
+        accts.marketing_account.key(),
+        accts.ceo_account.key(),
+    );
+
+    for pda in [&accts.vault, &accts.giveaway_pool] {
+        fund_rent_exempt(
+            &accts.authority.to_account_info(),
+            pda,
+            &accts.system_program.to_account_info(),
+        )?;
+    }
     //Err(BeanError::NotAllowedAuthority.into())
     Ok(())
 }
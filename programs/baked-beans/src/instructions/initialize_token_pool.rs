@@ -0,0 +1,121 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+#[instruction(pool_id: u64)]
+pub struct InitializeTokenPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // pools are created by the root pool's authority
+    #[account(
+        seeds = [GLOBAL_STATE_SEED, &ROOT_POOL_ID.to_le_bytes()],
+        bump,
+        has_one = authority @ BeanError::NotAllowedAuthority,
+    )]
+    pub root_state: Account<'info, GlobalState>,
+
+    #[account(constraint = mint.owner.eq(&spl_token::ID) @ BeanError::InvalidTokenAccount)]
+    /// CHECK: the deposit mint of the pool
+    pub mint: AccountInfo<'info>,
+
+    #[account(
+        init,
+        seeds = [GLOBAL_STATE_SEED, &pool_id.to_le_bytes()],
+        bump,
+        space = 8 + size_of::<GlobalState>(),
+        payer = authority,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+        bump,
+        space = 8 + size_of::<FeeSchedule>() + size_of::<FeeRecipient>() * MAX_FEE_RECIPIENTS,
+        payer = authority,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    /// CHECK: this should be set by admin
+    pub dev_account: AccountInfo<'info>,
+    /// CHECK: this should be set by admin
+    pub marketing_account: AccountInfo<'info>,
+    /// CHECK: this should be set by admin
+    pub ceo_account: AccountInfo<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: owner of the token vault
+    pub vault_authority: AccountInfo<'info>,
+
+    /// CHECK: checked to be a token account of the mint owned by the vault authority
+    pub token_vault: AccountInfo<'info>,
+
+    #[account(
+        seeds = [GIVEAWAY_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: owner of the giveaway token account
+    pub giveaway_authority: AccountInfo<'info>,
+
+    /// CHECK: checked to be a token account of the mint owned by the giveaway authority
+    pub giveaway_token_account: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> InitializeTokenPool<'info> {
+    pub fn validate(&self, price_per_bean: u64) -> Result<()> {
+        require!(price_per_bean > 0, BeanError::InvalidAction);
+        for (account, owner) in [
+            (&self.token_vault, &self.vault_authority),
+            (&self.giveaway_token_account, &self.giveaway_authority),
+        ] {
+            let token_account = load_token_account(account)?;
+            require!(
+                token_account.mint.eq(self.mint.key) && token_account.owner.eq(owner.key),
+                BeanError::InvalidTokenAccount
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Open pool `pool_id` taking deposits in `mint`, a bean costs `price_per_bean` of its base units.
+/// The token accounts are created beforehand and owned by the pool's PDAs.
+/// `migrated_from` names the pool this one succeeds, default for none, see `migrate_vault`
+#[access_control(ctx.accounts.validate(price_per_bean))]
+pub fn handle(ctx: Context<InitializeTokenPool>, pool_id: u64, price_per_bean: u64, migrated_from: Pubkey) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.is_initialized = 1;
+    accts.global_state.authority = accts.authority.key();
+    accts.global_state.pool_id = pool_id;
+    accts.global_state.mint = accts.mint.key();
+    accts.global_state.price_per_bean = price_per_bean;
+    accts.global_state.vault = accts.token_vault.key();
+    accts.global_state.giveaway_pool = accts.giveaway_token_account.key();
+    accts.global_state.tax_schedule = default_tax_schedule();
+    accts.global_state.max_payout_multiplier = DEFAULT_MAX_PAYOUT_MULTIPLIER;
+    accts.global_state.migrated_from = migrated_from;
+
+    accts.fee_schedule.bump = *ctx.bumps.get("fee_schedule").unwrap();
+    accts.fee_schedule.pool = accts.global_state.key();
+    accts.fee_schedule.recipients = default_fee_recipients(
+        accts.dev_account.key(),
+        accts.marketing_account.key(),
+        accts.ceo_account.key(),
+    );
+
+    emit!(EventPoolCreated {
+      pool: accts.global_state.key(),
+      pool_id,
+      mint: accts.global_state.mint,
+      authority: accts.global_state.authority
+    });
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct LockBeans<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+        constraint = user_state.position_mint == Pubkey::default() @ BeanError::PositionTokenized,
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + size_of::<LockPosition>(),
+        seeds = [LOCK_SEED, user_state.key().as_ref(), &user_state.lock_count.to_le_bytes()],
+        bump
+    )]
+    pub lock_position: Account<'info, LockPosition>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> LockBeans<'info> {
+    pub fn validate(&self, lock_days: u64, beans: u64) -> Result<()> {
+        require!(lock_boost(lock_days).is_some(), BeanError::InvalidLockPeriod);
+        require!(beans > 0 && beans <= self.user_state.beans, BeanError::InsufficientAmount);
+        require!(self.user_state.active_locks < MAX_LOCKS, BeanError::TooManyLocks);
+        Ok(())
+    }
+}
+
+/// Lock `beans` for `lock_days` to earn a boosted daily reward on them.
+/// Rewards up to now are accrued first, the user's other lock positions are passed as remaining accounts
+#[access_control(ctx.accounts.validate(lock_days, beans))]
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, LockBeans<'info>>, lock_days: u64, beans: u64) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let accts = ctx.accounts;
+
+    let mut locks = load_locks(ctx.remaining_accounts, &accts.user_state, accts.user_state.active_locks)?;
+    let mut positions = lock_positions(&locks);
+    accts.user_state.accrued_beans = settle_locks(&accts.user_state, &mut positions, cur_timestamp);
+    accts.user_state.baked_at = cur_timestamp;
+    store_locks(&mut locks, &positions, ctx.program_id)?;
+
+    let lock_id = accts.user_state.lock_count;
+    let boost = lock_boost(lock_days).unwrap();
+    let unlock_at = cur_timestamp + lock_days * SECONDS_PER_DAY;
+    accts.user_state.beans -= beans;
+    accts.user_state.locked_beans += beans;
+    accts.user_state.lock_count += 1;
+    accts.user_state.active_locks += 1;
+
+    accts.lock_position.bump = *ctx.bumps.get("lock_position").unwrap();
+    accts.lock_position.pool = accts.global_state.key();
+    accts.lock_position.user = accts.user.key();
+    accts.lock_position.lock_id = lock_id;
+    accts.lock_position.beans = beans;
+    accts.lock_position.boost = boost;
+    accts.lock_position.locked_at = cur_timestamp;
+    accts.lock_position.unlock_at = unlock_at;
+
+    emit!(EventBeansLocked {
+      pool: accts.global_state.key(),
+      user_address: accts.user.key(),
+      lock_id,
+      beans,
+      boost,
+      unlock_at
+    });
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::{prelude::*, AccountsClose};
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct MigrateUserState<'info> {
+    // the user, or the holder of their position NFT
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut, address = user_state.user)]
+    /// CHECK: refunded the rent of the old user state and lock positions
+    pub user: AccountInfo<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = is_migrated(&global_state) @ BeanError::PoolNotMigrated,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(mut, address = global_state.migrated_to)]
+    pub target_pool: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user_state.user.as_ref()],
+        bump = user_state.bump,
+        close = user,
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + size_of::<UserState>() + 32 * 100 + 32 * MAX_WHITELISTED_RECIPIENTS,
+        seeds = [USER_STATE_SEED, target_pool.key().as_ref(), user_state.user.as_ref()],
+        bump
+    )]
+    pub new_user_state: Account<'info, UserState>,
+
+    /// CHECK: holder's token account of the position NFT, ignored for positions without one
+    pub position_token: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> MigrateUserState<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let owner = position_owner(&self.user_state, &self.position_token)?;
+        require!(owner.eq(&self.owner.key()), BeanError::NotPositionHolder);
+        Ok(())
+    }
+}
+
+/// Move a user of a migrated pool into the pool its vault moved to, with their beans,
+/// cycle, payouts and settled rewards. Lock positions, passed as remaining accounts,
+/// are closed and their beans and rewards so far returned to the user.
+/// Fails if the user already has a state in the target pool
+#[access_control(ctx.accounts.validate())]
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, MigrateUserState<'info>>) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let accts = ctx.accounts;
+
+    let locks = load_locks(ctx.remaining_accounts, &accts.user_state, accts.user_state.active_locks)?;
+    let mut positions = lock_positions(&locks);
+    let mut pending = settle_locks(&accts.user_state, &mut positions, cur_timestamp);
+    let mut beans = accts.user_state.beans;
+    for (lock, position) in locks.iter().zip(positions.iter()) {
+      require!(lock.to_account_info().is_writable, BeanError::InvalidLockPosition);
+      beans += position.beans;
+      pending += position.accrued_beans;
+      lock.close(accts.user.clone())?;
+    }
+
+    let mut user_state = (*accts.user_state).clone();
+    user_state.bump = *ctx.bumps.get("new_user_state").unwrap();
+    user_state.pool = accts.target_pool.key();
+    user_state.beans = beans;
+    user_state.accrued_beans = pending;
+    user_state.baked_at = cur_timestamp;
+    user_state.locked_beans = 0;
+    user_state.active_locks = 0;
+    user_state.giveaway_round = 0;
+    user_state.giveaway_deposit = 0;
+    *accts.new_user_state = user_state;
+
+    if accts.user_state.total_deposit > 0 {
+      accts.global_state.total_bakers -= 1;
+      accts.target_pool.total_bakers += 1;
+    }
+
+    emit!(EventUserStateMigrated {
+      pool: accts.global_state.key(),
+      target_pool: accts.target_pool.key(),
+      user_address: accts.user_state.user,
+      beans,
+      accrued_beans: pending
+    });
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+use solana_program::{program::invoke_signed, system_instruction};
+
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = is_admin(&global_state, admin.key()) @ BeanError::NotAllowedAuthority,
+      constraint = global_state.paused == 1 @ BeanError::PoolNotPaused,
+      constraint = !is_migrated(&global_state) @ BeanError::PoolMigrated,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(
+        mut,
+        seeds = [QUEUED_ACTION_SEED, global_state.key().as_ref(), &queued_action.action_id.to_le_bytes()],
+        bump = queued_action.bump,
+        close = proposer,
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    #[account(mut, address = queued_action.proposer)]
+    /// CHECK: refunded the proposal's rent
+    pub proposer: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: the SOL vault, or the owner of the token vault
+    pub vault_authority: AccountInfo<'info>,
+
+    #[account(mut, address = global_state.vault)]
+    /// CHECK: the pool's vault, the vault authority itself for SOL pools
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [GIVEAWAY_SEED, global_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: the SOL giveaway pool, or the owner of the giveaway token account
+    pub giveaway_authority: AccountInfo<'info>,
+
+    #[account(mut, address = global_state.giveaway_pool)]
+    /// CHECK: the pool's giveaway pool, the giveaway authority itself for SOL pools
+    pub giveaway_pool: AccountInfo<'info>,
+
+    // successor pool the vault moves to, checked against the queued action in validate
+    #[account(mut)]
+    pub target_pool: Account<'info, GlobalState>,
+
+    #[account(mut, address = target_pool.vault)]
+    /// CHECK: the target pool's vault
+    pub new_vault: AccountInfo<'info>,
+
+    #[account(mut, address = target_pool.giveaway_pool)]
+    /// CHECK: the target pool's giveaway pool
+    pub new_giveaway_pool: AccountInfo<'info>,
+
+    #[account(address = spl_token::ID)]
+    /// CHECK: spl token program
+    pub token_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateVault<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+        let (target_pool, new_vault) = match self.queued_action.action {
+          AdminAction::MigrateVault { target_pool, new_vault } => (target_pool, new_vault),
+          _ => return Err(BeanError::InvalidAction.into()),
+        };
+        require!(
+            self.queued_action.eta - self.queued_action.queued_at >= MIN_MIGRATION_DELAY,
+            BeanError::ActionNotReady
+        );
+        require!(cur_timestamp >= self.queued_action.eta, BeanError::ActionNotReady);
+        // fee recipients claim from the old vault, which empties
+        require!(
+            self.fee_schedule.recipients.iter().all(|recipient| recipient.accrued == 0),
+            BeanError::UnclaimedFees
+        );
+        require!(
+            approval_count(&self.global_state, &self.queued_action) >= self.global_state.admin_threshold,
+            BeanError::NotEnoughApprovals
+        );
+        require!(
+            self.target_pool.key().eq(&target_pool) && self.new_vault.key().eq(&new_vault),
+            BeanError::InvalidMigrationTarget
+        );
+        require!(
+            target_pool.ne(&self.global_state.key()) && self.target_pool.mint.eq(&self.global_state.mint),
+            BeanError::InvalidMigrationTarget
+        );
+        // the target was created as this pool's successor and is run by the same admins
+        require!(
+            self.target_pool.migrated_from.eq(&self.global_state.key()) &&
+              self.target_pool.authority.eq(&self.global_state.authority) &&
+              self.target_pool.admin_threshold == self.global_state.admin_threshold &&
+              self.target_pool.admins == self.global_state.admins,
+            BeanError::InvalidMigrationTarget
+        );
+        // the giveaway pool moves along, so every round must have been drawn
+        require!(
+            self.global_state.giveaway_drawn_round == self.global_state.giveaway_round,
+            BeanError::GiveawayNotDrawn
+        );
+
+        // funds only ever land in a vault this program controls
+        let (target_vault, _) = Pubkey::find_program_address(&[VAULT_SEED, target_pool.as_ref()], &crate::ID);
+        if is_native_pool(&self.global_state) {
+          require!(new_vault.eq(&target_vault), BeanError::InvalidMigrationTarget);
+        } else {
+          let destination = load_token_account(&self.new_vault)?;
+          require!(
+            destination.mint.eq(&self.global_state.mint) && destination.owner.eq(&target_vault),
+            BeanError::InvalidMigrationTarget
+          );
+        }
+        Ok(())
+    }
+}
+
+/// Move the whole vault and giveaway pool to a successor pool, created with `migrated_from` set to this pool.
+/// Needs a queued MigrateVault action past its delay, the pool paused, its fees claimed and its giveaway rounds drawn.
+/// The pool is then marked migrated and can't be unpaused, its users move over with `migrate_user_state`
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<MigrateVault>) -> Result<()> {
+    let accts = ctx.accounts;
+    let pool = accts.global_state.key();
+    let vault_bump = *ctx.bumps.get("vault_authority").unwrap();
+    let signer_seeds: &[&[&[u8]]] = &[&[VAULT_SEED, pool.as_ref(), &[vault_bump]]];
+
+    let amount = if is_native_pool(&accts.global_state) {
+      let lamports = accts.vault.lamports();
+      invoke_signed(
+        &system_instruction::transfer(&accts.vault.key(), &accts.new_vault.key(), lamports),
+        &[
+          accts.vault.clone(),
+          accts.new_vault.clone(),
+          accts.system_program.to_account_info(),
+        ],
+        signer_seeds,
+      )?;
+      lamports
+    } else {
+      let tokens = load_token_account(&accts.vault)?.amount;
+      transfer_tokens(
+        &accts.token_program,
+        &accts.vault,
+        &accts.new_vault,
+        &accts.vault_authority,
+        tokens,
+        signer_seeds,
+      )?;
+      tokens
+    };
+
+    let giveaway_bump = *ctx.bumps.get("giveaway_authority").unwrap();
+    let giveaway_seeds: &[&[&[u8]]] = &[&[GIVEAWAY_SEED, pool.as_ref(), &[giveaway_bump]]];
+    let giveaway_amount = if is_native_pool(&accts.global_state) {
+      let lamports = accts.giveaway_pool.lamports();
+      invoke_signed(
+        &system_instruction::transfer(&accts.giveaway_pool.key(), &accts.new_giveaway_pool.key(), lamports),
+        &[
+          accts.giveaway_pool.clone(),
+          accts.new_giveaway_pool.clone(),
+          accts.system_program.to_account_info(),
+        ],
+        giveaway_seeds,
+      )?;
+      lamports
+    } else {
+      let tokens = load_token_account(&accts.giveaway_pool)?.amount;
+      transfer_tokens(
+        &accts.token_program,
+        &accts.giveaway_pool,
+        &accts.new_giveaway_pool,
+        &accts.giveaway_authority,
+        tokens,
+        giveaway_seeds,
+      )?;
+      tokens
+    };
+
+    // the users' funds now back the target pool
+    accts.target_pool.tvl += accts.global_state.tvl;
+    accts.global_state.tvl = 0;
+
+    let old_vault = accts.global_state.vault;
+    accts.global_state.vault = accts.new_vault.key();
+    accts.global_state.migrated_to = accts.target_pool.key();
+
+    emit!(EventVaultMigrated {
+      pool,
+      admin: accts.admin.key(),
+      action_id: accts.queued_action.action_id,
+      target_pool: accts.target_pool.key(),
+      old_vault,
+      new_vault: accts.new_vault.key(),
+      amount,
+      giveaway_amount
+    });
+    Ok(())
+}
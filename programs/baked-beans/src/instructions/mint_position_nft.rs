@@ -0,0 +1,145 @@
+use crate::{constants::*, error::*, states::*, events::*};
+use anchor_lang::prelude::*;
+use solana_program::{
+    program::{invoke, invoke_signed},
+    program_pack::Pack,
+    system_instruction,
+};
+use spl_token::instruction::AuthorityType;
+
+#[derive(Accounts)]
+pub struct MintPositionNft<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+        constraint = user_state.position_mint == Pubkey::default() @ BeanError::PositionTokenized,
+        // only the user can unlock, so locked beans would be stuck with a new holder
+        constraint = user_state.active_locks == 0 @ BeanError::LocksStillOpen,
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_MINT_SEED, user_state.key().as_ref()],
+        bump
+    )]
+    /// CHECK: created here as the position's mint
+    pub position_mint: AccountInfo<'info>,
+
+    // fresh keypair, created here as the user's token account holding the NFT
+    #[account(mut)]
+    pub position_token: Signer<'info>,
+
+    #[account(address = spl_token::ID)]
+    /// CHECK: spl token program
+    pub token_program: AccountInfo<'info>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Mint a supply 1 NFT for the user's position, whoever holds it then bakes and eats it.
+/// Delegate, whitelist and auto-bake settings are cleared and can't be set anymore
+pub fn handle(ctx: Context<MintPositionNft>) -> Result<()> {
+    let accts = ctx.accounts;
+    let bump = *ctx.bumps.get("position_mint").unwrap();
+    let user_state_key = accts.user_state.key();
+    let mint_key = accts.position_mint.key();
+    let user_key = accts.user.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[POSITION_MINT_SEED, user_state_key.as_ref(), &[bump]]];
+    let token_program = accts.token_program.key();
+    let rent_info = accts.rent.to_account_info();
+
+    // mint, with its own PDA as the mint authority
+    invoke_signed(
+        &system_instruction::create_account(
+            &user_key,
+            &mint_key,
+            accts.rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &token_program,
+        ),
+        &[
+            accts.user.to_account_info(),
+            accts.position_mint.clone(),
+            accts.system_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+    invoke(
+        &spl_token::instruction::initialize_mint(&token_program, &mint_key, &mint_key, None, 0)?,
+        &[accts.position_mint.clone(), rent_info.clone()],
+    )?;
+
+    // the user's token account
+    invoke(
+        &system_instruction::create_account(
+            &user_key,
+            accts.position_token.key,
+            accts.rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &token_program,
+        ),
+        &[
+            accts.user.to_account_info(),
+            accts.position_token.to_account_info(),
+            accts.system_program.to_account_info(),
+        ],
+    )?;
+    invoke(
+        &spl_token::instruction::initialize_account(&token_program, accts.position_token.key, &mint_key, &user_key)?,
+        &[
+            accts.position_token.to_account_info(),
+            accts.position_mint.clone(),
+            accts.user.to_account_info(),
+            rent_info,
+        ],
+    )?;
+
+    // mint the single token and drop the mint authority
+    invoke_signed(
+        &spl_token::instruction::mint_to(&token_program, &mint_key, accts.position_token.key, &mint_key, &[], 1)?,
+        &[
+            accts.position_mint.clone(),
+            accts.position_token.to_account_info(),
+            accts.token_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+    invoke_signed(
+        &spl_token::instruction::set_authority(
+            &token_program,
+            &mint_key,
+            None,
+            AuthorityType::MintTokens,
+            &mint_key,
+            &[],
+        )?,
+        &[accts.position_mint.clone(), accts.token_program.clone()],
+        signer_seeds,
+    )?;
+
+    accts.user_state.position_mint = mint_key;
+    accts.user_state.delegate = Pubkey::default();
+    accts.user_state.delegate_can_eat = 0;
+    accts.user_state.recipient_whitelist = vec![];
+    accts.user_state.auto_bake_interval = 0;
+    accts.user_state.auto_bake_tip = 0;
+
+    emit!(EventPositionTokenized {
+      pool: accts.global_state.key(),
+      user_address: user_key,
+      position_mint: mint_key
+    });
+    Ok(())
+}
@@ -1,6 +1,9 @@
 pub mod initialize;
 pub use initialize::*;
 
+pub mod reconfigure;
+pub use reconfigure::*;
+
 pub mod buy_beans;
 pub use buy_beans::*;
 
@@ -12,3 +15,119 @@ pub use eat_beans::*;
 
 pub mod init_user_state;
 pub use init_user_state::*;
+
+pub mod set_user_init_policy;
+pub use set_user_init_policy::*;
+
+pub mod lock_beans;
+pub use lock_beans::*;
+
+pub mod unlock_beans;
+pub use unlock_beans::*;
+
+pub mod mint_position_nft;
+pub use mint_position_nft::*;
+
+pub mod close_user_state;
+pub use close_user_state::*;
+
+pub mod set_auto_bake;
+pub use set_auto_bake::*;
+
+pub mod crank_bake;
+pub use crank_bake::*;
+
+pub mod set_delegate;
+pub use set_delegate::*;
+
+pub mod revoke_delegate;
+pub use revoke_delegate::*;
+
+pub mod set_recipient_whitelist;
+pub use set_recipient_whitelist::*;
+
+pub mod harvest;
+
+pub mod set_tax_schedule;
+pub use set_tax_schedule::*;
+
+pub mod set_max_payout_multiplier;
+pub use set_max_payout_multiplier::*;
+
+pub mod set_launch_schedule;
+pub use set_launch_schedule::*;
+
+pub mod set_pool_limits;
+pub use set_pool_limits::*;
+
+pub mod quote_eat;
+pub use quote_eat::*;
+
+pub mod add_fee_recipient;
+pub use add_fee_recipient::*;
+
+pub mod remove_fee_recipient;
+pub use remove_fee_recipient::*;
+
+pub mod set_fee_recipient_weight;
+pub use set_fee_recipient_weight::*;
+
+pub mod claim_fees;
+pub use claim_fees::*;
+
+pub mod start_giveaway_round;
+pub use start_giveaway_round::*;
+
+pub mod enter_giveaway;
+pub use enter_giveaway::*;
+
+pub mod reveal_giveaway_secret;
+pub use reveal_giveaway_secret::*;
+
+pub mod draw_giveaway;
+pub use draw_giveaway::*;
+
+pub mod initialize_token_pool;
+pub use initialize_token_pool::*;
+
+pub mod buy_beans_token;
+pub use buy_beans_token::*;
+
+pub mod eat_beans_token;
+pub use eat_beans_token::*;
+
+pub mod claim_fees_token;
+pub use claim_fees_token::*;
+
+pub mod draw_giveaway_token;
+pub use draw_giveaway_token::*;
+
+pub mod create_pool;
+pub use create_pool::*;
+
+pub mod set_paused;
+pub use set_paused::*;
+
+pub mod set_admin_delay;
+pub use set_admin_delay::*;
+
+pub mod set_admins;
+pub use set_admins::*;
+
+pub mod queue_admin_action;
+pub use queue_admin_action::*;
+
+pub mod execute_admin_action;
+pub use execute_admin_action::*;
+
+pub mod cancel_admin_action;
+pub use cancel_admin_action::*;
+
+pub mod approve_admin_action;
+pub use approve_admin_action::*;
+
+pub mod migrate_vault;
+pub use migrate_vault::*;
+
+pub mod migrate_user_state;
+pub use migrate_user_state::*;
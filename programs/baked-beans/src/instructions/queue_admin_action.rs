@@ -0,0 +1,77 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct QueueAdminAction<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      constraint = is_admin(&global_state, admin.key()) @ BeanError::NotAllowedAuthority,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    #[account(
+        init,
+        payer = admin,
+        // room for the approvals and the keys of the largest action
+        space = 8 + size_of::<QueuedAction>() + 2 * (4 + 32 * MAX_ADMINS),
+        seeds = [QUEUED_ACTION_SEED, global_state.key().as_ref(), &global_state.action_count.to_le_bytes()],
+        bump
+    )]
+    pub queued_action: Account<'info, QueuedAction>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> QueueAdminAction<'info> {
+    pub fn validate(&self, action: &AdminAction) -> Result<()> {
+        check_admin_action(&self.global_state, &self.fee_schedule, action)?;
+        Ok(())
+    }
+}
+
+/// Propose an admin change, executable once the pool's admin delay has passed,
+/// or MIN_MIGRATION_DELAY if longer for a vault migration,
+/// and, with a multisig, once enough admins approved it. The proposal counts as the proposer's approval
+#[access_control(ctx.accounts.validate(&action))]
+pub fn handle(ctx: Context<QueueAdminAction>, action: AdminAction) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let accts = ctx.accounts;
+    let action_id = accts.global_state.action_count;
+    let mut delay = accts.global_state.admin_delay;
+    if let AdminAction::MigrateVault { .. } = action {
+      delay = delay.max(MIN_MIGRATION_DELAY);
+    }
+    let eta = cur_timestamp + delay;
+
+    accts.queued_action.bump = *ctx.bumps.get("queued_action").unwrap();
+    accts.queued_action.pool = accts.global_state.key();
+    accts.queued_action.action_id = action_id;
+    accts.queued_action.proposer = accts.admin.key();
+    accts.queued_action.action = action.clone();
+    accts.queued_action.queued_at = cur_timestamp;
+    accts.queued_action.eta = eta;
+    accts.queued_action.approvals = vec![accts.admin.key()];
+    accts.global_state.action_count += 1;
+
+    emit!(EventAdminActionQueued {
+      pool: accts.global_state.key(),
+      action_id,
+      proposer: accts.admin.key(),
+      action,
+      eta
+    });
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::{constants::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct QuoteEat<'info> {
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user_state.user.as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+/// Read only, emits what eating all pending rewards would pay right now
+/// and when the cooldown and withdrawal limit allow the next eat.
+/// Meant to be simulated by clients, with the user's lock positions as remaining accounts
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, QuoteEat<'info>>) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let accts = ctx.accounts;
+    let tax_schedule = &accts.global_state.tax_schedule;
+    let user_state = &accts.user_state;
+
+    let locks = load_locks(ctx.remaining_accounts, user_state, user_state.active_locks)?;
+    let pending_beans = settle_locks(user_state, &mut lock_positions(&locks), cur_timestamp);
+    let beans_in_sol_before_fee = beans_to_amount(pending_beans, accts.global_state.price_per_bean);
+    let remaining_payout = max_payout(&accts.global_state, user_state).saturating_sub(user_state.cycle_payout);
+    let (_, _, sol_to_eat, _) = split_eat(beans_in_sol_before_fee, has_bean_taxed(tax_schedule, user_state), remaining_payout);
+
+    emit!(EventEatQuote {
+      user_address: user_state.user,
+      pending_beans,
+      days_since_last_eat: days_since_last_eat(user_state),
+      tax: has_bean_taxed(tax_schedule, user_state),
+      sol_to_eat,
+      next_eat_at: next_eat_at(&accts.global_state, user_state, cur_timestamp),
+      withdrawal_allowance: withdrawal_allowance(&accts.global_state, user_state, cur_timestamp)
+    });
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Reconfigure<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+
+    /// CHECK: this should be set by admin
+    pub dev_account: AccountInfo<'info>,
+    /// CHECK: this should be set by admin
+    pub marketing_account: AccountInfo<'info>,
+    /// CHECK: this should be set by admin
+    pub ceo_account: AccountInfo<'info>,
+}
+
+impl<'info> Reconfigure<'info> {
+    pub fn validate(&self, new_authority: Pubkey) -> Result<()> {
+        check_reconfigure(&self.fee_schedule, new_authority)?;
+        Ok(())
+    }
+}
+
+/// Redo what initialize set up: hand the pool to `new_authority` and reset the fee
+/// recipients to the given accounts, once their fees are claimed.
+/// Queued as AdminAction::Reconfigure when the timelock or the multisig is on
+#[access_control(ctx.accounts.validate(new_authority))]
+pub fn handle(ctx: Context<Reconfigure>, new_authority: Pubkey) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.authority = new_authority;
+    accts.fee_schedule.recipients = default_fee_recipients(
+        accts.dev_account.key(),
+        accts.marketing_account.key(),
+        accts.ceo_account.key(),
+    );
+
+    emit!(EventReconfigured {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      new_authority,
+      recipients: accts.fee_schedule.recipients.clone()
+    });
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveFeeRecipient<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+      mut,
+      seeds = [FEE_SCHEDULE_SEED, global_state.key().as_ref()],
+      bump = fee_schedule.bump,
+    )]
+    pub fee_schedule: Account<'info, FeeSchedule>,
+}
+
+impl<'info> RemoveFeeRecipient<'info> {
+    pub fn validate(&self, account: Pubkey) -> Result<()> {
+        check_remove_fee_recipient(&self.fee_schedule, account)?;
+        Ok(())
+    }
+}
+
+/// Remove a fee recipient once its fees are claimed,
+/// its share stays in the vault until reassigned
+#[access_control(ctx.accounts.validate(account))]
+pub fn handle(ctx: Context<RemoveFeeRecipient>, account: Pubkey) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.fee_schedule.recipients.retain(|recipient| recipient.account.ne(&account));
+
+    emit!(EventFeeScheduleUpdated {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      recipients: accts.fee_schedule.recipients.clone()
+    });
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::{constants::*, error::*, states::*, events::*};
+use anchor_lang::prelude::*;
+use solana_program::hash::hashv;
+
+#[derive(Accounts)]
+pub struct RevealGiveawaySecret<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GIVEAWAY_ROUND_SEED, giveaway_round.pool.as_ref(), &giveaway_round.round_id.to_le_bytes()],
+        bump = giveaway_round.bump,
+    )]
+    pub giveaway_round: Account<'info, GiveawayRound>,
+}
+
+impl<'info> RevealGiveawaySecret<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(cur_timestamp > self.giveaway_round.end_time, BeanError::GiveawayRoundOpen);
+        require!(
+            cur_timestamp <= self.giveaway_round.reveal_deadline,
+            BeanError::GiveawayRoundClosed
+        );
+        Ok(())
+    }
+}
+
+/// Mix a participant's secret into the round seed, after entries close
+/// and before the authority draws. Tickets that are never revealed can't win
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<RevealGiveawaySecret>, secret: [u8; 32]) -> Result<()> {
+    let accts = ctx.accounts;
+    let user_key = accts.user.key();
+    let round = &mut accts.giveaway_round;
+
+    let participant = round.participants
+      .iter_mut()
+      .find(|participant| participant.user.eq(&user_key));
+    require!(participant.is_some(), BeanError::NotEligibleForGiveaway);
+
+    let participant = participant.unwrap();
+    require!(participant.revealed == 0, BeanError::InvalidAction);
+    require!(
+      hashv(&[user_key.as_ref(), &secret]).to_bytes() == participant.commitment,
+      BeanError::InvalidGiveawaySecret
+    );
+    participant.revealed = 1;
+    round.seed = hashv(&[&round.seed, &secret]).to_bytes();
+
+    emit!(EventGiveawaySecretRevealed {
+      pool: round.pool,
+      round_id: round.round_id,
+      user_address: user_key
+    });
+    Ok(())
+}
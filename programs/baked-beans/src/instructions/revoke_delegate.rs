@@ -0,0 +1,39 @@
+use crate::{constants::*, error::*, states::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, user_state.pool.as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+impl<'info> RevokeDelegate<'info> {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.user_state.delegate.ne(&Pubkey::default()),
+            BeanError::NotAllowedDelegate
+        );
+        Ok(())
+    }
+}
+
+#[access_control(ctx.accounts.validate())]
+pub fn handle(ctx: Context<RevokeDelegate>) -> Result<()> {
+    let accts = ctx.accounts;
+    let delegate = accts.user_state.delegate;
+    accts.user_state.delegate = Pubkey::default();
+    accts.user_state.delegate_can_eat = 0;
+
+    emit!(EventDelegateRevoked {
+      user_address: accts.user.key(),
+      delegate
+    });
+    Ok(())
+}
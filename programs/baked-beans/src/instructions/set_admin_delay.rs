@@ -0,0 +1,38 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAdminDelay<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> SetAdminDelay<'info> {
+    pub fn validate(&self, admin_delay: u64) -> Result<()> {
+        require!(admin_delay <= MAX_ADMIN_DELAY, BeanError::InvalidAdminDelay);
+        Ok(())
+    }
+}
+
+/// Turn on the timelock: admin changes must then be queued and wait `admin_delay` seconds,
+/// including later changes of the delay itself
+#[access_control(ctx.accounts.validate(admin_delay))]
+pub fn handle(ctx: Context<SetAdminDelay>, admin_delay: u64) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.admin_delay = admin_delay;
+
+    emit!(EventAdminDelaySet {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      admin_delay
+    });
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAdmins<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> SetAdmins<'info> {
+    pub fn validate(&self, admins: &[Pubkey], admin_threshold: u8) -> Result<()> {
+        check_admins(admins, admin_threshold)?;
+        Ok(())
+    }
+}
+
+/// Hand admin actions to an M-of-N admin set: they are then proposed with queue_admin_action
+/// and execute once `admin_threshold` admins approved. Later changes of the set need its approval too
+#[access_control(ctx.accounts.validate(&admins, admin_threshold))]
+pub fn handle(ctx: Context<SetAdmins>, admins: Vec<Pubkey>, admin_threshold: u8) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.admin_threshold = admin_threshold;
+    accts.global_state.admins = [Pubkey::default(); MAX_ADMINS];
+    accts.global_state.admins[..admins.len()].copy_from_slice(&admins);
+
+    emit!(EventAdminsSet {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      admins,
+      admin_threshold
+    });
+    Ok(())
+}
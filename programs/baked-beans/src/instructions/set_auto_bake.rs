@@ -0,0 +1,42 @@
+use crate::{constants::*, error::*, states::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAutoBake<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, user_state.pool.as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+        constraint = user_state.position_mint == Pubkey::default() @ BeanError::PositionTokenized,
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+impl<'info> SetAutoBake<'info> {
+    pub fn validate(&self, interval: u64, tip: u64) -> Result<()> {
+        require!(
+            interval == 0 || interval >= MIN_AUTO_BAKE_INTERVAL,
+            BeanError::InvalidAutoBakeSettings
+        );
+        require!(tip <= MAX_CRANK_TIP, BeanError::InvalidAutoBakeSettings);
+        Ok(())
+    }
+}
+
+/// Opt in to (or out of, with zero interval) permissionless auto-compounding
+#[access_control(ctx.accounts.validate(interval, tip))]
+pub fn handle(ctx: Context<SetAutoBake>, interval: u64, tip: u64) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.user_state.auto_bake_interval = interval;
+    accts.user_state.auto_bake_tip = tip;
+
+    emit!(EventAutoBakeSet {
+      user_address: accts.user.key(),
+      interval,
+      tip
+    });
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::{constants::*, error::*, states::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, user_state.pool.as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+        constraint = user_state.position_mint == Pubkey::default() @ BeanError::PositionTokenized,
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+impl<'info> SetDelegate<'info> {
+    pub fn validate(&self, delegate: Pubkey, can_eat: u8) -> Result<()> {
+        require!(delegate.ne(&Pubkey::default()), BeanError::ZeroAddressDetected);
+        require!(delegate.ne(&self.user.key()), BeanError::NotAllowedDelegate);
+        require!(can_eat <= 1, BeanError::InvalidAction);
+        Ok(())
+    }
+}
+
+/// Allow `delegate` to bake, and optionally eat, on the user's behalf
+#[access_control(ctx.accounts.validate(delegate, can_eat))]
+pub fn handle(ctx: Context<SetDelegate>, delegate: Pubkey, can_eat: u8) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.user_state.delegate = delegate;
+    accts.user_state.delegate_can_eat = can_eat;
+
+    emit!(EventDelegateSet {
+      user_address: accts.user.key(),
+      delegate,
+      can_eat
+    });
+    Ok(())
+}
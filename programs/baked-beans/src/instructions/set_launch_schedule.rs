@@ -0,0 +1,42 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetLaunchSchedule<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> SetLaunchSchedule<'info> {
+    pub fn validate(&self, launch_time: u64, whitelist_end: u64, whitelist_root: &[u8; 32]) -> Result<()> {
+        check_launch_schedule(launch_time, whitelist_end, whitelist_root)?;
+        Ok(())
+    }
+}
+
+/// Deposits open at `launch_time`, restricted to users in `whitelist_root`
+/// until `whitelist_end`. A `whitelist_end` not after `launch_time` skips the whitelist phase
+#[access_control(ctx.accounts.validate(launch_time, whitelist_end, &whitelist_root))]
+pub fn handle(ctx: Context<SetLaunchSchedule>, launch_time: u64, whitelist_end: u64, whitelist_root: [u8; 32]) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.launch_time = launch_time;
+    accts.global_state.whitelist_end = whitelist_end;
+    accts.global_state.whitelist_root = whitelist_root;
+
+    emit!(EventLaunchScheduleSet {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      launch_time,
+      whitelist_end,
+      whitelist_root
+    });
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMaxPayoutMultiplier<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> SetMaxPayoutMultiplier<'info> {
+    pub fn validate(&self, max_payout_multiplier: u64) -> Result<()> {
+        require!(max_payout_multiplier >= BPS_DENOMINATOR, BeanError::InvalidPayoutMultiplier);
+        Ok(())
+    }
+}
+
+/// Set how much a deposit cycle pays out, in basis points of its deposits.
+/// Applies to open cycles too
+#[access_control(ctx.accounts.validate(max_payout_multiplier))]
+pub fn handle(ctx: Context<SetMaxPayoutMultiplier>, max_payout_multiplier: u64) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.max_payout_multiplier = max_payout_multiplier;
+
+    emit!(EventMaxPayoutMultiplierSet {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      max_payout_multiplier
+    });
+    Ok(())
+}
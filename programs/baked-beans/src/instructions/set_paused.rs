@@ -0,0 +1,39 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> SetPaused<'info> {
+    pub fn validate(&self, paused: u8) -> Result<()> {
+        require!(paused <= 1, BeanError::InvalidAction);
+        // lifting a pause goes through the queue when the timelock or the multisig is on
+        require!(paused == 1 || direct_admin_allowed(&self.global_state), BeanError::TimelockActive);
+        require!(paused == 1 || !is_migrated(&self.global_state), BeanError::PoolMigrated);
+        Ok(())
+    }
+}
+
+/// Halt or resume deposits, eats and crank tips, bakes keep working. Pausing always applies at once
+#[access_control(ctx.accounts.validate(paused))]
+pub fn handle(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.paused = paused;
+
+    emit!(EventPausedSet {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      paused
+    });
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPoolLimits<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> SetPoolLimits<'info> {
+    pub fn validate(&self, limits: &PoolLimits) -> Result<()> {
+        check_pool_limits(limits)?;
+        Ok(())
+    }
+}
+
+/// Set the pool TVL cap and the per-epoch deposit and withdrawal limits.
+/// Changing the epoch length restarts the epoch counters
+#[access_control(ctx.accounts.validate(&limits))]
+pub fn handle(ctx: Context<SetPoolLimits>, limits: PoolLimits) -> Result<()> {
+    let accts = ctx.accounts;
+    if limits.epoch_length != accts.global_state.limits.epoch_length {
+      accts.global_state.epoch_start = 0;
+    }
+    accts.global_state.limits = limits;
+
+    emit!(EventPoolLimitsSet {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      limits
+    });
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use crate::{constants::*, error::*, states::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRecipientWhitelist<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, user_state.pool.as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+        constraint = user_state.position_mint == Pubkey::default() @ BeanError::PositionTokenized,
+    )]
+    pub user_state: Account<'info, UserState>,
+}
+
+impl<'info> SetRecipientWhitelist<'info> {
+    pub fn validate(&self, recipients: &[Pubkey]) -> Result<()> {
+        require!(recipients.len() <= MAX_WHITELISTED_RECIPIENTS, BeanError::TooManyRecipients);
+        require!(
+            recipients.iter().all(|recipient| recipient.ne(&Pubkey::default())),
+            BeanError::ZeroAddressDetected
+        );
+        Ok(())
+    }
+}
+
+/// Replace the user's withdrawal whitelist, an empty list allows any recipient
+#[access_control(ctx.accounts.validate(&recipients))]
+pub fn handle(ctx: Context<SetRecipientWhitelist>, recipients: Vec<Pubkey>) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.user_state.recipient_whitelist = recipients.clone();
+
+    emit!(EventRecipientWhitelistSet {
+      user_address: accts.user.key(),
+      recipients
+    });
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetTaxSchedule<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> SetTaxSchedule<'info> {
+    pub fn validate(&self, tax_schedule: &TaxSchedule) -> Result<()> {
+        require!(valid_tax_schedule(tax_schedule), BeanError::InvalidTaxSchedule);
+        Ok(())
+    }
+}
+
+/// Replace the withdrawal tax schedule used by eat_beans
+#[access_control(ctx.accounts.validate(&tax_schedule))]
+pub fn handle(ctx: Context<SetTaxSchedule>, tax_schedule: TaxSchedule) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.tax_schedule = tax_schedule;
+
+    emit!(EventTaxScheduleSet {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      tax_schedule
+    });
+    Ok(())
+}
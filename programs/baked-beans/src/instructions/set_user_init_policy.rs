@@ -0,0 +1,41 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetUserInitPolicy<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+      constraint = direct_admin_allowed(&global_state) @ BeanError::TimelockActive,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+impl<'info> SetUserInitPolicy<'info> {
+    pub fn validate(&self, restrict_user_init: u8, relayers: &[Pubkey]) -> Result<()> {
+        check_user_init_policy(restrict_user_init, relayers)?;
+        Ok(())
+    }
+}
+
+/// Choose who may pay for init_user_state: anyone, or when restricted
+/// only the user themselves and the given relayers
+#[access_control(ctx.accounts.validate(restrict_user_init, &relayers))]
+pub fn handle(ctx: Context<SetUserInitPolicy>, restrict_user_init: u8, relayers: Vec<Pubkey>) -> Result<()> {
+    let accts = ctx.accounts;
+    accts.global_state.restrict_user_init = restrict_user_init;
+    accts.global_state.relayers = [Pubkey::default(); MAX_RELAYERS];
+    accts.global_state.relayers[..relayers.len()].copy_from_slice(&relayers);
+
+    emit!(EventUserInitPolicySet {
+      pool: accts.global_state.key(),
+      authority: accts.authority.key(),
+      restrict_user_init,
+      relayers
+    });
+    Ok(())
+}
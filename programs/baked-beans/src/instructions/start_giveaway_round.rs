@@ -0,0 +1,74 @@
+use crate::{constants::*, error::*, states::*, events::*};
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+#[derive(Accounts)]
+pub struct StartGiveawayRound<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+      mut,
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+      has_one = authority @ BeanError::NotAllowedAuthority,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        init,
+        seeds = [GIVEAWAY_ROUND_SEED, global_state.key().as_ref(), &(global_state.giveaway_round + 1).to_le_bytes()],
+        bump,
+        space = 8 + size_of::<GiveawayRound>() + size_of::<GiveawayTicket>() * MAX_GIVEAWAY_PARTICIPANTS,
+        payer = authority,
+    )]
+    pub giveaway_round: Account<'info, GiveawayRound>,
+
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> StartGiveawayRound<'info> {
+    pub fn validate(&self, end_time: u64, reveal_deadline: u64) -> Result<()> {
+        let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(cur_timestamp > self.global_state.giveaway_round_end, BeanError::GiveawayRoundOpen);
+        require!(
+            self.global_state.giveaway_drawn_round == self.global_state.giveaway_round,
+            BeanError::GiveawayNotDrawn
+        );
+        require!(cur_timestamp < end_time && end_time < reveal_deadline, BeanError::InvalidAction);
+        Ok(())
+    }
+}
+
+/// Open a giveaway round, deposits until `end_time` earn tickets.
+/// The authority commits to a secret it has to reveal to draw the round,
+/// and can't open the next round until it does
+#[access_control(ctx.accounts.validate(end_time, reveal_deadline))]
+pub fn handle(
+    ctx: Context<StartGiveawayRound>,
+    end_time: u64,
+    reveal_deadline: u64,
+    authority_commitment: [u8; 32],
+) -> Result<()> {
+    let accts = ctx.accounts;
+    let round_id = accts.global_state.giveaway_round + 1;
+    accts.global_state.giveaway_round = round_id;
+    accts.global_state.giveaway_round_end = end_time;
+
+    accts.giveaway_round.bump = *ctx.bumps.get("giveaway_round").unwrap();
+    accts.giveaway_round.pool = accts.global_state.key();
+    accts.giveaway_round.round_id = round_id;
+    accts.giveaway_round.end_time = end_time;
+    accts.giveaway_round.reveal_deadline = reveal_deadline;
+    accts.giveaway_round.authority_commitment = authority_commitment;
+
+    emit!(EventGiveawayRoundStarted {
+      pool: accts.global_state.key(),
+      round_id,
+      end_time,
+      reveal_deadline,
+      authority_commitment
+    });
+    Ok(())
+}
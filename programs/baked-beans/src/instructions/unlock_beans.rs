@@ -0,0 +1,73 @@
+use crate::{constants::*, error::*, states::*, utils::*, events::*};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UnlockBeans<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+      seeds = [GLOBAL_STATE_SEED, &global_state.pool_id.to_le_bytes()],
+      bump,
+    )]
+    pub global_state: Account<'info, GlobalState>,
+
+    #[account(
+        mut,
+        seeds = [USER_STATE_SEED, global_state.key().as_ref(), user.key().as_ref()],
+        bump = user_state.bump,
+        has_one = user,
+    )]
+    pub user_state: Account<'info, UserState>,
+
+    #[account(
+        mut,
+        seeds = [LOCK_SEED, user_state.key().as_ref(), &lock_position.lock_id.to_le_bytes()],
+        bump = lock_position.bump,
+        has_one = user,
+        close = user,
+    )]
+    pub lock_position: Account<'info, LockPosition>,
+}
+
+impl<'info> UnlockBeans<'info> {
+    pub fn validate(&self) -> Result<()> {
+        let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+        require!(cur_timestamp >= self.lock_position.unlock_at, BeanError::StillLocked);
+        Ok(())
+    }
+}
+
+/// Return an unlocked position's beans to the user's free beans and close it.
+/// Its held rewards become pending, the user's other lock positions are passed as remaining accounts
+#[access_control(ctx.accounts.validate())]
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, UnlockBeans<'info>>) -> Result<()> {
+    let cur_timestamp = Clock::get()?.unix_timestamp as u64;
+    let accts = ctx.accounts;
+    let lock_key = accts.lock_position.key();
+    require!(
+        !ctx.remaining_accounts.iter().any(|account| account.key.eq(&lock_key)),
+        BeanError::InvalidLockPosition
+    );
+
+    let mut locks = load_locks(ctx.remaining_accounts, &accts.user_state, accts.user_state.active_locks - 1)?;
+    let mut positions = lock_positions(&locks);
+    positions.push((*accts.lock_position).clone());
+    accts.user_state.accrued_beans = settle_locks(&accts.user_state, &mut positions, cur_timestamp);
+    accts.user_state.baked_at = cur_timestamp;
+    positions.pop();
+    store_locks(&mut locks, &positions, ctx.program_id)?;
+
+    let beans = accts.lock_position.beans;
+    accts.user_state.beans += beans;
+    accts.user_state.locked_beans -= beans;
+    accts.user_state.active_locks -= 1;
+
+    emit!(EventBeansUnlocked {
+      pool: accts.global_state.key(),
+      user_address: accts.user.key(),
+      lock_id: accts.lock_position.lock_id,
+      beans
+    });
+    Ok(())
+}
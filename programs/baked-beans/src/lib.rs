@@ -8,6 +8,7 @@ pub mod utils;
 pub mod events;
 
 use instructions::*;
+use states::*;
 
 declare_id!("CzBzTMfRhJViNwXC6fZTLHcfEDsn6xEM7dPjeCZ2HU1f");
 #[program]
@@ -18,19 +19,208 @@ pub mod baked_beans {
         initialize::handle(ctx, new_authority)
     }
 
-    pub fn buy_beans(ctx: Context<BuyBeans>, ref_user: Pubkey, amount: u64) -> Result<()> {
-        buy_beans::handle(ctx, ref_user, amount)
+    pub fn reconfigure(ctx: Context<Reconfigure>, new_authority: Pubkey) -> Result<()> {
+        reconfigure::handle(ctx, new_authority)
     }
 
-    pub fn eat_beans(ctx: Context<EatBeans>) -> Result<()> {
-        eat_beans::handle(ctx)
+    pub fn buy_beans(ctx: Context<BuyBeans>, ref_user: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        buy_beans::handle(ctx, ref_user, amount, proof)
     }
 
-    pub fn bake_beans(ctx: Context<BakeBeans>, only_rebaking: u8) -> Result<()> {
+    pub fn eat_beans<'info>(ctx: Context<'_, '_, '_, 'info, EatBeans<'info>>) -> Result<()> {
+        eat_beans::handle(ctx, None)
+    }
+
+    pub fn eat_beans_partial<'info>(ctx: Context<'_, '_, '_, 'info, EatBeans<'info>>, amount: u64) -> Result<()> {
+        eat_beans::handle(ctx, Some(amount))
+    }
+
+    pub fn harvest<'info>(ctx: Context<'_, '_, '_, 'info, EatBeans<'info>>, compound_bps: u64) -> Result<()> {
+        harvest::handle(ctx, compound_bps)
+    }
+
+    pub fn bake_beans<'info>(ctx: Context<'_, '_, '_, 'info, BakeBeans<'info>>, only_rebaking: u8) -> Result<()> {
         bake_beans::handle(ctx, only_rebaking)
     }
 
     pub fn init_user_state(ctx: Context<InitUserState>, user_key: Pubkey) -> Result<()> {
       init_user_state::handle(ctx, user_key)
     }
+
+    pub fn set_user_init_policy(ctx: Context<SetUserInitPolicy>, restrict_user_init: u8, relayers: Vec<Pubkey>) -> Result<()> {
+        set_user_init_policy::handle(ctx, restrict_user_init, relayers)
+    }
+
+    pub fn lock_beans<'info>(ctx: Context<'_, '_, '_, 'info, LockBeans<'info>>, lock_days: u64, beans: u64) -> Result<()> {
+        lock_beans::handle(ctx, lock_days, beans)
+    }
+
+    pub fn unlock_beans<'info>(ctx: Context<'_, '_, '_, 'info, UnlockBeans<'info>>) -> Result<()> {
+        unlock_beans::handle(ctx)
+    }
+
+    pub fn mint_position_nft(ctx: Context<MintPositionNft>) -> Result<()> {
+        mint_position_nft::handle(ctx)
+    }
+
+    pub fn close_user_state(ctx: Context<CloseUserState>) -> Result<()> {
+        close_user_state::handle(ctx)
+    }
+
+    pub fn set_auto_bake(ctx: Context<SetAutoBake>, interval: u64, tip: u64) -> Result<()> {
+        set_auto_bake::handle(ctx, interval, tip)
+    }
+
+    pub fn crank_bake<'info>(ctx: Context<'_, '_, '_, 'info, CrankBake<'info>>) -> Result<()> {
+        crank_bake::handle(ctx)
+    }
+
+    pub fn set_delegate(ctx: Context<SetDelegate>, delegate: Pubkey, can_eat: u8) -> Result<()> {
+        set_delegate::handle(ctx, delegate, can_eat)
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        revoke_delegate::handle(ctx)
+    }
+
+    pub fn set_recipient_whitelist(ctx: Context<SetRecipientWhitelist>, recipients: Vec<Pubkey>) -> Result<()> {
+        set_recipient_whitelist::handle(ctx, recipients)
+    }
+
+    pub fn set_tax_schedule(ctx: Context<SetTaxSchedule>, tax_schedule: TaxSchedule) -> Result<()> {
+        set_tax_schedule::handle(ctx, tax_schedule)
+    }
+
+    pub fn set_max_payout_multiplier(ctx: Context<SetMaxPayoutMultiplier>, max_payout_multiplier: u64) -> Result<()> {
+        set_max_payout_multiplier::handle(ctx, max_payout_multiplier)
+    }
+
+    pub fn set_launch_schedule(
+        ctx: Context<SetLaunchSchedule>,
+        launch_time: u64,
+        whitelist_end: u64,
+        whitelist_root: [u8; 32],
+    ) -> Result<()> {
+        set_launch_schedule::handle(ctx, launch_time, whitelist_end, whitelist_root)
+    }
+
+    pub fn set_pool_limits(ctx: Context<SetPoolLimits>, limits: PoolLimits) -> Result<()> {
+        set_pool_limits::handle(ctx, limits)
+    }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+        set_paused::handle(ctx, paused)
+    }
+
+    pub fn set_admin_delay(ctx: Context<SetAdminDelay>, admin_delay: u64) -> Result<()> {
+        set_admin_delay::handle(ctx, admin_delay)
+    }
+
+    pub fn set_admins(ctx: Context<SetAdmins>, admins: Vec<Pubkey>, admin_threshold: u8) -> Result<()> {
+        set_admins::handle(ctx, admins, admin_threshold)
+    }
+
+    pub fn queue_admin_action(ctx: Context<QueueAdminAction>, action: AdminAction) -> Result<()> {
+        queue_admin_action::handle(ctx, action)
+    }
+
+    pub fn approve_admin_action(ctx: Context<ApproveAdminAction>) -> Result<()> {
+        approve_admin_action::handle(ctx)
+    }
+
+    pub fn execute_admin_action(ctx: Context<ExecuteAdminAction>) -> Result<()> {
+        execute_admin_action::handle(ctx)
+    }
+
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        migrate_vault::handle(ctx)
+    }
+
+    pub fn migrate_user_state<'info>(ctx: Context<'_, '_, '_, 'info, MigrateUserState<'info>>) -> Result<()> {
+        migrate_user_state::handle(ctx)
+    }
+
+    pub fn cancel_admin_action(ctx: Context<CancelAdminAction>) -> Result<()> {
+        cancel_admin_action::handle(ctx)
+    }
+
+    pub fn quote_eat<'info>(ctx: Context<'_, '_, '_, 'info, QuoteEat<'info>>) -> Result<()> {
+        quote_eat::handle(ctx)
+    }
+
+    pub fn add_fee_recipient(ctx: Context<AddFeeRecipient>, account: Pubkey, weight: u64) -> Result<()> {
+        add_fee_recipient::handle(ctx, account, weight)
+    }
+
+    pub fn remove_fee_recipient(ctx: Context<RemoveFeeRecipient>, account: Pubkey) -> Result<()> {
+        remove_fee_recipient::handle(ctx, account)
+    }
+
+    pub fn set_fee_recipient_weight(ctx: Context<SetFeeRecipientWeight>, account: Pubkey, weight: u64) -> Result<()> {
+        set_fee_recipient_weight::handle(ctx, account, weight)
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        claim_fees::handle(ctx)
+    }
+
+    pub fn start_giveaway_round(
+        ctx: Context<StartGiveawayRound>,
+        end_time: u64,
+        reveal_deadline: u64,
+        authority_commitment: [u8; 32],
+    ) -> Result<()> {
+        start_giveaway_round::handle(ctx, end_time, reveal_deadline, authority_commitment)
+    }
+
+    pub fn enter_giveaway(ctx: Context<EnterGiveaway>, commitment: [u8; 32]) -> Result<()> {
+        enter_giveaway::handle(ctx, commitment)
+    }
+
+    pub fn reveal_giveaway_secret(ctx: Context<RevealGiveawaySecret>, secret: [u8; 32]) -> Result<()> {
+        reveal_giveaway_secret::handle(ctx, secret)
+    }
+
+    pub fn draw_giveaway(ctx: Context<DrawGiveaway>, secret: [u8; 32]) -> Result<()> {
+        draw_giveaway::handle(ctx, secret)
+    }
+
+    pub fn initialize_token_pool(
+        ctx: Context<InitializeTokenPool>,
+        pool_id: u64,
+        price_per_bean: u64,
+        migrated_from: Pubkey,
+    ) -> Result<()> {
+        initialize_token_pool::handle(ctx, pool_id, price_per_bean, migrated_from)
+    }
+
+    pub fn create_pool(
+        ctx: Context<CreatePool>,
+        pool_id: u64,
+        new_authority: Pubkey,
+        tax_schedule: TaxSchedule,
+        migrated_from: Pubkey,
+    ) -> Result<()> {
+        create_pool::handle(ctx, pool_id, new_authority, tax_schedule, migrated_from)
+    }
+
+    pub fn buy_beans_token(ctx: Context<BuyBeansToken>, ref_user: Pubkey, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        buy_beans_token::handle(ctx, ref_user, amount, proof)
+    }
+
+    pub fn eat_beans_token<'info>(ctx: Context<'_, '_, '_, 'info, EatBeansToken<'info>>) -> Result<()> {
+        eat_beans_token::handle(ctx, None)
+    }
+
+    pub fn eat_beans_token_partial<'info>(ctx: Context<'_, '_, '_, 'info, EatBeansToken<'info>>, amount: u64) -> Result<()> {
+        eat_beans_token::handle(ctx, Some(amount))
+    }
+
+    pub fn claim_fees_token(ctx: Context<ClaimFeesToken>) -> Result<()> {
+        claim_fees_token::handle(ctx)
+    }
+
+    pub fn draw_giveaway_token(ctx: Context<DrawGiveawayToken>, secret: [u8; 32]) -> Result<()> {
+        draw_giveaway_token::handle(ctx, secret)
+    }
 }
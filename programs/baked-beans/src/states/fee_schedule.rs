@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct FeeSchedule {
+    pub bump: u8,
+    // global state of the pool
+    pub pool: Pubkey,
+
+    pub recipients: Vec<FeeRecipient>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeRecipient {
+    pub account: Pubkey,
+    // basis points of the collected fee, the unassigned rest stays in the vault
+    pub weight: u64,
+    // fees kept in the vault until claimed
+    pub accrued: u64,
+}
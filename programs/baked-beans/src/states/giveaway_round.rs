@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct GiveawayRound {
+    pub bump: u8,
+    // global state of the pool
+    pub pool: Pubkey,
+    pub round_id: u64,
+
+    // deposits made until end_time earn tickets
+    pub end_time: u64,
+    // participants can reveal their secrets until reveal_deadline
+    pub reveal_deadline: u64,
+
+    // hash of the secret the authority reveals at the draw
+    pub authority_commitment: [u8; 32],
+    // running hash of revealed secrets
+    pub seed: [u8; 32],
+
+    pub total_tickets: u64,
+    pub participants: Vec<GiveawayTicket>,
+
+    pub is_drawn: u8,
+    pub winner: Pubkey,
+    pub prize: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct GiveawayTicket {
+    pub user: Pubkey,
+    pub tickets: u64,
+    // hash of the participant's key and secret, only revealed tickets can win
+    pub commitment: [u8; 32],
+    pub revealed: u8,
+}
@@ -1,3 +1,4 @@
+use crate::constants::*;
 use anchor_lang::prelude::*;
 
 #[account]
@@ -7,16 +8,94 @@ pub struct GlobalState {
     pub is_initialized: u8,
     // admin
     pub authority: Pubkey,
-    // vault
+    // seeds the global state, the vault and everything else in the pool
+    pub pool_id: u64,
+    // deposit mint, NATIVE_MINT for SOL pools
+    pub mint: Pubkey,
+    // deposit units per bean, replaces SOL_PER_BEAN for token pools
+    pub price_per_bean: u64,
+    // vault, a token account owned by the vault PDA for token pools
     pub vault: Pubkey,
-    // dev_account
-    pub dev_account: Pubkey,
-    // marketing_account
-    pub marketing_account: Pubkey,
-    // giveaway_account
-    pub giveaway_account: Pubkey,
-    // ceo_account
-    pub ceo_account: Pubkey,
-
-    pub total_bakers: u64
+    // giveaway pool, a token account owned by the giveaway PDA for token pools
+    pub giveaway_pool: Pubkey,
+    // current giveaway round and the end of its deposit window
+    pub giveaway_round: u64,
+    pub giveaway_round_end: u64,
+    // last drawn round, a round can only start once the previous one is drawn
+    pub giveaway_drawn_round: u64,
+
+    pub total_bakers: u64,
+    // fee remainders and rounding dust kept in the vault
+    pub retained_fees: u64,
+
+    // withdrawal tax by days since last eat
+    pub tax_schedule: TaxSchedule,
+    // max payout of a cycle in basis points of its deposits
+    pub max_payout_multiplier: u64,
+
+    // no deposits before launch_time, and only whitelisted users until whitelist_end
+    pub launch_time: u64,
+    pub whitelist_end: u64,
+    // merkle root over hashes of whitelisted user keys
+    pub whitelist_root: [u8; 32],
+
+    // funds held by the vault, in the pool's units: deposits net of payouts, giveaway shares and claimed fees
+    pub tvl: u64,
+    pub limits: PoolLimits,
+    // flows of the current epoch, counted against the limits
+    pub epoch_start: u64,
+    pub epoch_deposits: u64,
+    pub epoch_withdrawals: u64,
+
+    // 1 while deposits, eats and crank tips are halted
+    pub paused: u8,
+    // pool whose vault and users may migrate into this one, set at creation
+    pub migrated_from: Pubkey,
+    // pool the vault moved to, the pool then stays paused
+    pub migrated_to: Pubkey,
+    // seconds a queued admin action waits before it can execute, 0 applies admin changes directly
+    pub admin_delay: u64,
+    // id of the next queued admin action
+    pub action_count: u64,
+    // approvals of distinct admins a queued action needs, 0 leaves admin actions to the authority
+    pub admin_threshold: u8,
+    // default for unused slots
+    pub admins: [Pubkey; MAX_ADMINS],
+
+    // 1 if init_user_state must be paid by the user or an allow-listed relayer
+    pub restrict_user_init: u8,
+    // default for unused slots
+    pub relayers: [Pubkey; MAX_RELAYERS],
+}
+
+// in the pool's units, 0 for no limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PoolLimits {
+    pub max_tvl: u64,
+    // seconds, 0 disables the per-epoch limits
+    pub epoch_length: u64,
+    pub epoch_deposit_limit: u64,
+    pub epoch_withdrawal_limit: u64,
+    // seconds between two eats of a user
+    pub eat_cooldown: u64,
+    // payouts a user can eat in a rolling day
+    pub user_daily_withdrawal_limit: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TaxSchedule {
+    // TAX_CURVE_TABLE, TAX_CURVE_LINEAR or TAX_CURVE_EXPONENTIAL
+    pub curve: u8,
+    // 1 to restart the schedule every `period` days
+    pub cyclic: u8,
+    // days in a cycle, and table entries in use for TAX_CURVE_TABLE
+    pub period: u8,
+    // tax in basis points by day for TAX_CURVE_TABLE
+    pub table: [u16; MAX_TAX_TABLE_DAYS],
+    // tax in basis points on day 0 for the linear and exponential curves
+    pub start_tax: u64,
+    // tax floor in basis points for the linear and exponential curves
+    pub min_tax: u64,
+    // linear: basis points dropped per day, exponential: basis points of tax kept per day
+    pub decay: u64,
 }
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct LockPosition {
+    pub bump: u8,
+    // global state of the pool
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub lock_id: u64,
+
+    // beans moved out of the user's free beans until unlock_at
+    pub beans: u64,
+    // basis points added to the daily reward of the locked beans
+    pub boost: u64,
+    pub locked_at: u64,
+    pub unlock_at: u64,
+
+    // rewards earned while locked, released once unlocked
+    pub accrued_beans: u64,
+}
@@ -3,3 +3,15 @@ pub use global_state::*;
 
 pub mod user_state;
 pub use user_state::*;
+
+pub mod fee_schedule;
+pub use fee_schedule::*;
+
+pub mod giveaway_round;
+pub use giveaway_round::*;
+
+pub mod lock_position;
+pub use lock_position::*;
+
+pub mod queued_action;
+pub use queued_action::*;
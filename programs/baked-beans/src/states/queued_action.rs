@@ -0,0 +1,40 @@
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct QueuedAction {
+    pub bump: u8,
+    // global state of the pool
+    pub pool: Pubkey,
+    pub action_id: u64,
+    // refunded the account's rent when it closes
+    pub proposer: Pubkey,
+
+    pub action: AdminAction,
+    pub queued_at: u64,
+    // earliest time the action can be executed
+    pub eta: u64,
+    // admins who approved the action, the proposer included
+    pub approvals: Vec<Pubkey>,
+}
+
+// admin changes that go through the timelock once the pool's admin delay is set
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub enum AdminAction {
+    SetTaxSchedule { tax_schedule: TaxSchedule },
+    SetMaxPayoutMultiplier { max_payout_multiplier: u64 },
+    SetPoolLimits { limits: PoolLimits },
+    SetLaunchSchedule { launch_time: u64, whitelist_end: u64, whitelist_root: [u8; 32] },
+    SetUserInitPolicy { restrict_user_init: u8, relayers: Vec<Pubkey> },
+    AddFeeRecipient { account: Pubkey, weight: u64 },
+    RemoveFeeRecipient { account: Pubkey },
+    SetFeeRecipientWeight { account: Pubkey, weight: u64 },
+    SetAdminDelay { admin_delay: u64 },
+    SetAdmins { admins: Vec<Pubkey>, admin_threshold: u8 },
+    Reconfigure { new_authority: Pubkey, dev_account: Pubkey, marketing_account: Pubkey, ceo_account: Pubkey },
+    // executed by migrate_vault, new_vault is the vault of target_pool
+    MigrateVault { target_pool: Pubkey, new_vault: Pubkey },
+    #[default]
+    Unpause,
+}
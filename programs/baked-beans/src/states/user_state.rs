@@ -7,20 +7,58 @@ pub struct UserState {
 
     // user
     pub user: Pubkey,
+    // global state of the pool
+    pub pool: Pubkey,
 
     pub total_deposit: u64,
     pub total_payout: u64,
 
+    // deposit cycle, a deposit after the max payout starts the next one
+    pub cycle: u64,
+    pub cycle_deposit: u64,
+    pub cycle_payout: u64,
+
     // first deposit time
     pub first_deposit_time: u64,
     pub ate_at: u64,
     pub baked_at: u64,
     
     pub beans: u64,
+    // rewards left unclaimed by a partial eat
+    pub accrued_beans: u64,
+    // beans held in lock positions, the next lock id and the open positions
+    pub locked_beans: u64,
+    pub lock_count: u64,
+    pub active_locks: u64,
     pub upline: Pubkey,
     
     pub has_referred: u8,
+
+    // giveaway round of the last deposit and beans bought during it
+    pub giveaway_round: u64,
+    pub giveaway_deposit: u64,
     
+    // auto-compound interval in seconds, 0 when disabled
+    pub auto_bake_interval: u64,
+    // basis points of rewards tipped to the cranker on each auto-compound
+    pub auto_bake_tip: u64,
+
+    // operator allowed to bake on the user's behalf, default when unset
+    pub delegate: Pubkey,
+    // 1 if the delegate may also eat, proceeds still go to the user
+    pub delegate_can_eat: u8,
+
     pub referrals: Vec<Pubkey>,
     pub bonus_eligible_referrals: Vec<Pubkey>,
+
+    // start of the user's withdrawal day and what was eaten since
+    pub withdrawal_window_start: u64,
+    pub window_withdrawals: u64,
+
+    // mint of the position NFT, default when the position isn't tokenized.
+    // Its holder controls baking and eating instead of the user
+    pub position_mint: Pubkey,
+
+    // allowed withdrawal destinations besides the user, any when empty
+    pub recipient_whitelist: Vec<Pubkey>,
 }
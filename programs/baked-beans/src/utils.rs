@@ -1,8 +1,17 @@
-use crate::{constants::*, states::*};
+use crate::{constants::*, error::*, states::*};
 use anchor_lang::prelude::*;
+use solana_program::{hash::{hash, hashv}, program::{invoke, invoke_signed}, program_pack::Pack, system_instruction};
 
-pub fn percent_from_amount(amount: u64, fee: u64) -> u64 {
-  amount * fee / 100
+/// `bps` basis points of `amount`, rounded down, for amounts paid out of the vault
+pub fn bps_from_amount(amount: u64, bps: u64) -> u64 {
+  (amount as u128 * bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// `bps` basis points of `amount`, rounded up, for fees and taxes charged to users
+#[allow(clippy::manual_div_ceil)]
+pub fn bps_from_amount_ceil(amount: u64, bps: u64) -> u64 {
+  let denominator = BPS_DENOMINATOR as u128;
+  ((amount as u128 * bps as u128 + denominator - 1) / denominator) as u64
 }
 
 pub fn sol_to_beans(sol_amount: u64) -> u64 {
@@ -13,27 +22,104 @@ pub fn beans_to_sol(beans_amount: u64) -> u64 {
   beans_amount * SOL_PER_BEAN
 }
 
+pub fn amount_to_beans(amount: u64, price_per_bean: u64) -> u64 {
+  amount / price_per_bean
+}
+
+pub fn beans_to_amount(beans_amount: u64, price_per_bean: u64) -> u64 {
+  beans_amount * price_per_bean
+}
+
+pub fn is_native_pool(global_state: &GlobalState) -> bool {
+  global_state.mint.eq(&NATIVE_MINT)
+}
+
+pub fn load_token_account(account: &AccountInfo) -> Result<spl_token::state::Account> {
+  require!(account.owner.eq(&spl_token::ID), BeanError::InvalidTokenAccount);
+  let token_account = spl_token::state::Account::unpack(&account.try_borrow_data()?)?;
+  Ok(token_account)
+}
+
+pub fn transfer_tokens<'info>(
+  token_program: &AccountInfo<'info>,
+  from: &AccountInfo<'info>,
+  to: &AccountInfo<'info>,
+  authority: &AccountInfo<'info>,
+  amount: u64,
+  signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+  invoke_signed(
+    &spl_token::instruction::transfer(token_program.key, from.key, to.key, authority.key, &[], amount)?,
+    &[from.clone(), to.clone(), authority.clone(), token_program.clone()],
+    signer_seeds,
+  )?;
+  Ok(())
+}
+
+/// dev, marketing and ceo accounts seed the fee schedule of a new pool
+pub fn default_fee_recipients(dev: Pubkey, marketing: Pubkey, ceo: Pubkey) -> Vec<FeeRecipient> {
+  vec![
+    FeeRecipient { account: dev, weight: DEV_FEE, accrued: 0 },
+    FeeRecipient { account: marketing, weight: MARKETING_FEE, accrued: 0 },
+    FeeRecipient { account: ceo, weight: CEO_FEE, accrued: 0 },
+  ]
+}
+
+/// Top up a lamport holding PDA of a new pool to the rent exempt minimum
+pub fn fund_rent_exempt<'info>(
+  payer: &AccountInfo<'info>,
+  pda: &AccountInfo<'info>,
+  system_program: &AccountInfo<'info>,
+) -> Result<()> {
+  let rent = Rent::default();
+  let required_lamports = rent
+    .minimum_balance(0)
+    .max(1)
+    .saturating_sub(pda.lamports());
+
+  invoke(
+    &system_instruction::transfer(payer.key, pda.key, required_lamports),
+    &[payer.clone(), pda.clone(), system_program.clone()],
+  )?;
+  Ok(())
+}
+
+/// locked beans count towards the wallet cap
 pub fn add_beans(user_state: &UserState, beans_to_add: u64) -> u64 {
   let total_beans = user_state.beans + beans_to_add;
-  let max_beans = sol_to_beans(MAX_WALLET_TVL_IN_SOL);
+  let max_beans = sol_to_beans(MAX_WALLET_TVL_IN_SOL).saturating_sub(user_state.locked_beans);
   if total_beans > max_beans { max_beans }
   else { total_beans }
 }
 
 pub fn max_tvl_reached(user_state: &UserState) -> bool {
-  user_state.beans >= sol_to_beans(MAX_WALLET_TVL_IN_SOL)
+  user_state.beans + user_state.locked_beans >= sol_to_beans(MAX_WALLET_TVL_IN_SOL)
 }
 
-pub fn max_payout_reached(user_state: &UserState) -> bool {
-  user_state.total_payout >= max_payout(user_state)
+pub fn max_payout_reached(global_state: &GlobalState, user_state: &UserState) -> bool {
+  user_state.cycle_payout >= max_payout(global_state, user_state)
 }
 
-pub fn max_payout(user_state: &UserState) -> u64 {
-  user_state.total_deposit * 3
+/// max payout of the user's current cycle
+pub fn max_payout(global_state: &GlobalState, user_state: &UserState) -> u64 {
+  bps_from_amount(user_state.cycle_deposit, global_state.max_payout_multiplier)
 }
 
-pub fn calc_giveaway_amount(user_state: &UserState, sol_withdrawal_amount: u64) -> u64 {
-  percent_from_amount(sol_withdrawal_amount, has_bean_taxed(user_state)) / 2
+pub fn add_payout(user_state: &mut UserState, amount: u64) {
+  user_state.total_payout += amount;
+  user_state.cycle_payout += amount;
+}
+
+/// Close a paid out cycle, beans and rewards left in it are forfeited.
+/// Lock positions must be closed first
+pub fn start_new_cycle(user_state: &mut UserState, cur_timestamp: u64) {
+  user_state.cycle += 1;
+  user_state.cycle_deposit = 0;
+  user_state.cycle_payout = 0;
+  user_state.beans = 0;
+  user_state.accrued_beans = 0;
+  user_state.ate_at = cur_timestamp;
+  user_state.baked_at = cur_timestamp;
 }
 
 pub fn seconds_since_last_eat(user_state: &UserState) -> u64 {
@@ -52,28 +138,75 @@ pub fn days_since_last_eat(user_state: &UserState) -> u64 {
   seconds_passed / SECONDS_PER_DAY
 }
 
-pub fn has_bean_taxed(user_state: &UserState) -> u64 {
+pub fn has_bean_taxed(tax_schedule: &TaxSchedule, user_state: &UserState) -> u64 {
   let days_passed: u64 = days_since_last_eat(user_state);
-  let last_digit = days_passed % 10;
+  tax_for_days(tax_schedule, days_passed)
+}
+
+pub fn tax_for_days(tax_schedule: &TaxSchedule, days_passed: u64) -> u64 {
+  let period = tax_schedule.period as u64;
+  let day = if tax_schedule.cyclic == 1 { days_passed % period } else { days_passed };
+
+  match tax_schedule.curve {
+    TAX_CURVE_TABLE => {
+      let last_day = period - 1;
+      tax_schedule.table[day.min(last_day) as usize] as u64
+    }
+    TAX_CURVE_LINEAR => {
+      let decayed = tax_schedule.decay.saturating_mul(day);
+      tax_schedule.start_tax.saturating_sub(decayed).max(tax_schedule.min_tax)
+    }
+    _ => {
+      let mut tax = tax_schedule.start_tax;
+      for _ in 0..day {
+        if tax <= tax_schedule.min_tax { break; }
+        tax = bps_from_amount(tax, tax_schedule.decay);
+      }
+      tax.max(tax_schedule.min_tax)
+    }
+  }
+}
+
+/// the original schedule: 90% on the day of the last eat, down 10% a day, repeating every 10 days
+pub fn default_tax_schedule() -> TaxSchedule {
+  let mut table = [0u16; MAX_TAX_TABLE_DAYS];
+  table[..10].copy_from_slice(&[9000, 8000, 7000, 6000, 5000, 4000, 3000, 2000, 1000, 0]);
+  TaxSchedule {
+    curve: TAX_CURVE_TABLE,
+    cyclic: 1,
+    period: 10,
+    table,
+    ..TaxSchedule::default()
+  }
+}
 
-  match last_digit {
-    0 => 90,
-    1 => 80,
-    2 => 70,
-    3 => 60,
-    4 => 50,
-    5 => 40,
-    6 => 30,
-    7 => 20,
-    8 => 10,
-    _ => 0
+pub fn valid_tax_schedule(tax_schedule: &TaxSchedule) -> bool {
+  let period = tax_schedule.period as usize;
+  if period == 0 || period > MAX_TAX_TABLE_DAYS {
+    return false;
+  }
+  match tax_schedule.curve {
+    TAX_CURVE_TABLE => tax_schedule.table[..period].iter().all(|&tax| tax as u64 <= BPS_DENOMINATOR),
+    TAX_CURVE_LINEAR =>
+      tax_schedule.start_tax <= BPS_DENOMINATOR && tax_schedule.min_tax <= tax_schedule.start_tax,
+    TAX_CURVE_EXPONENTIAL =>
+      tax_schedule.start_tax <= BPS_DENOMINATOR &&
+      tax_schedule.min_tax <= tax_schedule.start_tax &&
+      tax_schedule.decay < BPS_DENOMINATOR,
+    _ => false
   }
 }
 
-pub fn rewarded_beans(user_state: &UserState) -> u64 {
-  let seconds_passed = seconds_since_last_action(user_state);
+pub fn rewarded_beans(user_state: &UserState, locks: &[LockPosition], cur_timestamp: u64) -> u64 {
+  let seconds_passed = cur_timestamp - last_action_at(user_state);
   let daily_reward_factor = daily_reward(user_state);
-  let beans_rewarded = calc_beans_reward(user_state, seconds_passed, daily_reward_factor);
+  let beans_rewarded = calc_beans_reward(
+    user_state,
+    locks,
+    last_action_at(user_state),
+    seconds_passed,
+    daily_reward_factor,
+  );
 
   if beans_rewarded >= sol_to_beans(MAX_DAILY_REWARDS_IN_SOL) {
     sol_to_beans(MAX_DAILY_REWARDS_IN_SOL)
@@ -82,8 +215,35 @@ pub fn rewarded_beans(user_state: &UserState) -> u64 {
   }
 }
 
+pub fn pending_beans(user_state: &UserState, locks: &[LockPosition], cur_timestamp: u64) -> u64 {
+  rewarded_beans(user_state, locks, cur_timestamp) + user_state.accrued_beans
+}
+
+/// Pending rewards the user can bake or eat now. Rewards of positions still locked
+/// are held in them, and released by the first settle after they unlock
+pub fn settle_locks(user_state: &UserState, locks: &mut [LockPosition], cur_timestamp: u64) -> u64 {
+  let mut pending = pending_beans(user_state, locks, cur_timestamp);
+  let seconds_passed = cur_timestamp - last_action_at(user_state);
+  let daily_reward_factor = daily_reward(user_state);
+  for lock in locks.iter_mut() {
+    if cur_timestamp < lock.unlock_at {
+      let held = calc_lock_reward(lock, last_action_at(user_state), seconds_passed, daily_reward_factor).min(pending);
+      lock.accrued_beans += held;
+      pending -= held;
+    } else {
+      pending += lock.accrued_beans;
+      lock.accrued_beans = 0;
+    }
+  }
+  pending
+}
+
 pub fn seconds_since_last_action(user_state: &UserState) -> u64 {
   let cur_timestamp = Clock::get().unwrap().unix_timestamp as u64;
+  cur_timestamp - last_action_at(user_state)
+}
+
+pub fn last_action_at(user_state: &UserState) -> u64 {
   let mut last_timestamp = user_state.baked_at;
   
   if last_timestamp == 0 {
@@ -94,29 +254,475 @@ pub fn seconds_since_last_action(user_state: &UserState) -> u64 {
     last_timestamp = user_state.first_deposit_time;
   }
 
-  cur_timestamp - last_timestamp
+  last_timestamp
 }
 
+/// daily reward in basis points of the user's beans
 pub fn daily_reward(user_state: &UserState) -> u64 {
   let ref_count = user_state.bonus_eligible_referrals.len();
-  if ref_count < 10 { 30000 }
-  else if ref_count < 25 { 35000 }
-  else if ref_count < 50 { 40000 }
-  else if ref_count < 100 { 45000 }
-  else if ref_count < 150 { 50000 }
-  else if ref_count < 250 { 55000 }
-  else { 60000 }
+  if ref_count < 10 { 300 }
+  else if ref_count < 25 { 350 }
+  else if ref_count < 50 { 400 }
+  else if ref_count < 100 { 450 }
+  else if ref_count < 150 { 500 }
+  else if ref_count < 250 { 550 }
+  else { 600 }
+}
+
+/// Rewards of the user's free beans and of each lock position, with its boost
+pub fn calc_beans_reward(
+  user_state: &UserState,
+  locks: &[LockPosition],
+  since: u64,
+  seconds_passed: u64,
+  daily_reward_factor: u64,
+) -> u64 {
+  let reward_per_day = bps_from_amount(user_state.beans, daily_reward_factor) as u128;
+  let mut reward = (reward_per_day * seconds_passed as u128 / SECONDS_PER_DAY as u128) as u64;
+  for lock in locks.iter() {
+    reward += calc_lock_reward(lock, since, seconds_passed, daily_reward_factor);
+  }
+  reward
+}
+
+/// Reward of a lock position over `seconds_passed` from `since`,
+/// boosted until it unlocks and at the base rate after
+pub fn calc_lock_reward(lock: &LockPosition, since: u64, seconds_passed: u64, daily_reward_factor: u64) -> u64 {
+  let boosted_seconds = lock.unlock_at.saturating_sub(since).min(seconds_passed);
+  let boosted_per_day = bps_from_amount(lock.beans, daily_reward_factor + lock.boost) as u128;
+  let base_per_day = bps_from_amount(lock.beans, daily_reward_factor) as u128;
+  let reward = boosted_per_day * boosted_seconds as u128 + base_per_day * (seconds_passed - boosted_seconds) as u128;
+  (reward / SECONDS_PER_DAY as u128) as u64
+}
+
+pub fn lock_boost(lock_days: u64) -> Option<u64> {
+  LOCK_BOOSTS.iter().find(|(days, _)| *days == lock_days).map(|(_, boost)| *boost)
+}
+
+/// Deserialize the user's lock positions passed as remaining accounts, `expected` of them
+pub fn load_locks<'info>(
+  remaining_accounts: &[AccountInfo<'info>],
+  user_state: &UserState,
+  expected: u64,
+) -> Result<Vec<Account<'info, LockPosition>>> {
+  require!(remaining_accounts.len() as u64 == expected, BeanError::MissingLockPositions);
+  let mut locks: Vec<Account<'info, LockPosition>> = vec![];
+  for account in remaining_accounts.iter() {
+    let lock: Account<LockPosition> = Account::try_from(account)?;
+    require!(
+      lock.user.eq(&user_state.user) && lock.pool.eq(&user_state.pool),
+      BeanError::InvalidLockPosition
+    );
+    require!(!locks.iter().any(|other| other.key().eq(&lock.key())), BeanError::InvalidLockPosition);
+    locks.push(lock);
+  }
+  Ok(locks)
 }
 
-pub fn calc_beans_reward(user_state: &UserState, seconds_passed: u64, daily_reward_factor: u64) -> u64 {
-  let reward_per_day = percent_from_amount(user_state.beans, daily_reward_factor);
-  let rewards_per_second = reward_per_day * 1000 / SECONDS_PER_DAY;
-  let beans_rewarded = rewards_per_second * seconds_passed / 10_000_000;
-  beans_rewarded
+pub fn lock_positions(locks: &[Account<LockPosition>]) -> Vec<LockPosition> {
+  locks.iter().map(|lock| (**lock).clone()).collect()
 }
 
-pub fn add_withdrawal_taxes(user_state: &UserState, sol_withdrawal_amt: u64) -> u64{
-  percent_from_amount(sol_withdrawal_amt, 100u64 - has_bean_taxed(user_state))
+/// Write settled positions back to their accounts
+pub fn store_locks(locks: &mut [Account<LockPosition>], positions: &[LockPosition], program_id: &Pubkey) -> Result<()> {
+  for (lock, position) in locks.iter_mut().zip(positions.iter()) {
+    require!(lock.to_account_info().is_writable, BeanError::InvalidLockPosition);
+    lock.accrued_beans = position.accrued_beans;
+    lock.exit(program_id)?;
+  }
+  Ok(())
+}
+
+/// Split an eat of `amount_before_fee` taxed at `tax` bps with `remaining_payout` left in the cycle.
+/// The eat that reaches the max payout pays the rest of it untaxed.
+/// Returns the fee, the giveaway share, the amount to send and the payout to record
+pub fn split_eat(amount_before_fee: u64, tax: u64, remaining_payout: u64) -> (u64, u64, u64, u64) {
+  let total_fee = bps_from_amount_ceil(amount_before_fee, WITHDRAWAL_FEE);
+  let after_fee = amount_before_fee - total_fee;
+  let for_giveaway = bps_from_amount(after_fee, tax) / 2;
+
+  if amount_before_fee >= remaining_payout {
+    return (total_fee, for_giveaway, remaining_payout, remaining_payout);
+  }
+  let to_eat = after_fee - bps_from_amount_ceil(after_fee, tax);
+  let payout = amount_before_fee - bps_from_amount_ceil(amount_before_fee, tax);
+  (total_fee, for_giveaway, to_eat, payout)
+}
+
+pub fn total_fee_weight(fee_schedule: &FeeSchedule) -> u64 {
+  fee_schedule.recipients.iter().map(|recipient| recipient.weight).sum()
+}
+
+pub fn check_add_fee_recipient(fee_schedule: &FeeSchedule, account: Pubkey, weight: u64) -> Result<()> {
+  let recipients = &fee_schedule.recipients;
+  require!(account.ne(&Pubkey::default()), BeanError::ZeroAddressDetected);
+  require!(recipients.len() < MAX_FEE_RECIPIENTS, BeanError::TooManyFeeRecipients);
+  require!(
+    recipients.iter().all(|recipient| recipient.account.ne(&account)),
+    BeanError::FeeRecipientExists
+  );
+  require!(
+    total_fee_weight(fee_schedule) + weight <= BPS_DENOMINATOR,
+    BeanError::InvalidFeeWeights
+  );
+  Ok(())
+}
+
+pub fn check_remove_fee_recipient(fee_schedule: &FeeSchedule, account: Pubkey) -> Result<()> {
+  let recipient = fee_schedule.recipients
+    .iter()
+    .find(|recipient| recipient.account.eq(&account));
+  require!(recipient.is_some(), BeanError::FeeRecipientNotFound);
+  require!(recipient.unwrap().accrued == 0, BeanError::UnclaimedFees);
+  Ok(())
+}
+
+pub fn check_fee_recipient_weight(fee_schedule: &FeeSchedule, account: Pubkey, weight: u64) -> Result<()> {
+  let current = fee_schedule.recipients
+    .iter()
+    .find(|recipient| recipient.account.eq(&account));
+  require!(current.is_some(), BeanError::FeeRecipientNotFound);
+  require!(
+    total_fee_weight(fee_schedule) - current.unwrap().weight + weight <= BPS_DENOMINATOR,
+    BeanError::InvalidFeeWeights
+  );
+  Ok(())
+}
+
+pub fn check_launch_schedule(launch_time: u64, whitelist_end: u64, whitelist_root: &[u8; 32]) -> Result<()> {
+  // a whitelist phase needs a root to check proofs against
+  require!(
+    whitelist_end <= launch_time || *whitelist_root != [0u8; 32],
+    BeanError::InvalidLaunchSchedule
+  );
+  Ok(())
+}
+
+pub fn check_user_init_policy(restrict_user_init: u8, relayers: &[Pubkey]) -> Result<()> {
+  require!(restrict_user_init <= 1, BeanError::InvalidAction);
+  require!(relayers.len() <= MAX_RELAYERS, BeanError::TooManyRelayers);
+  require!(
+    relayers.iter().all(|relayer| relayer.ne(&Pubkey::default())),
+    BeanError::ZeroAddressDetected
+  );
+  Ok(())
+}
+
+/// Bounded periods, and per-epoch limits only with an epoch length
+pub fn check_pool_limits(limits: &PoolLimits) -> Result<()> {
+  require!(
+    limits.epoch_length <= MAX_EPOCH_LENGTH && limits.eat_cooldown <= MAX_EAT_COOLDOWN,
+    BeanError::InvalidPoolLimits
+  );
+  require!(
+    limits.epoch_length > 0 || (limits.epoch_deposit_limit == 0 && limits.epoch_withdrawal_limit == 0),
+    BeanError::InvalidPoolLimits
+  );
+  Ok(())
+}
+
+/// Fee recipients are replaced, so their fees must be claimed first
+pub fn check_reconfigure(fee_schedule: &FeeSchedule, new_authority: Pubkey) -> Result<()> {
+  require!(new_authority.ne(&Pubkey::default()), BeanError::ZeroAddressDetected);
+  require!(
+    fee_schedule.recipients.iter().all(|recipient| recipient.accrued == 0),
+    BeanError::UnclaimedFees
+  );
+  Ok(())
+}
+
+/// A threshold of 0 needs no admins, otherwise at most all of them
+pub fn check_admins(admins: &[Pubkey], admin_threshold: u8) -> Result<()> {
+  require!(admins.len() <= MAX_ADMINS, BeanError::InvalidAdmins);
+  require!(
+    (admin_threshold == 0) == admins.is_empty() && admin_threshold as usize <= admins.len(),
+    BeanError::InvalidAdmins
+  );
+  require!(
+    admins.iter().all(|admin| admin.ne(&Pubkey::default())),
+    BeanError::ZeroAddressDetected
+  );
+  require!(
+    admins.iter().enumerate().all(|(i, admin)| !admins[..i].contains(admin)),
+    BeanError::InvalidAdmins
+  );
+  Ok(())
+}
+
+/// Who can propose, approve, execute and cancel admin actions:
+/// the admins once a threshold is set, the authority otherwise
+pub fn is_admin(global_state: &GlobalState, key: Pubkey) -> bool {
+  if global_state.admin_threshold == 0 {
+    global_state.authority.eq(&key)
+  } else {
+    key.ne(&Pubkey::default()) && global_state.admins.contains(&key)
+  }
+}
+
+/// Approvals from keys that are still admins
+pub fn approval_count(global_state: &GlobalState, queued_action: &QueuedAction) -> u8 {
+  queued_action.approvals.iter().filter(|approver| is_admin(global_state, **approver)).count() as u8
+}
+
+pub fn is_migrated(global_state: &GlobalState) -> bool {
+  global_state.migrated_to.ne(&Pubkey::default())
+}
+
+/// Admin changes skip the queue only while neither the timelock nor the multisig is on
+pub fn direct_admin_allowed(global_state: &GlobalState) -> bool {
+  global_state.admin_delay == 0 && global_state.admin_threshold == 0
+}
+
+/// Checks an admin action against the current state, when queued and again when executed
+pub fn check_admin_action(global_state: &GlobalState, fee_schedule: &FeeSchedule, action: &AdminAction) -> Result<()> {
+  match action {
+    AdminAction::SetTaxSchedule { tax_schedule } => {
+      require!(valid_tax_schedule(tax_schedule), BeanError::InvalidTaxSchedule);
+    }
+    AdminAction::SetMaxPayoutMultiplier { max_payout_multiplier } => {
+      require!(*max_payout_multiplier >= BPS_DENOMINATOR, BeanError::InvalidPayoutMultiplier);
+    }
+    AdminAction::SetPoolLimits { limits } => {
+      check_pool_limits(limits)?;
+    }
+    AdminAction::Unpause => {
+      require!(!is_migrated(global_state), BeanError::PoolMigrated);
+    }
+    AdminAction::SetLaunchSchedule { launch_time, whitelist_end, whitelist_root } => {
+      check_launch_schedule(*launch_time, *whitelist_end, whitelist_root)?;
+    }
+    AdminAction::SetUserInitPolicy { restrict_user_init, relayers } => {
+      check_user_init_policy(*restrict_user_init, relayers)?;
+    }
+    AdminAction::AddFeeRecipient { account, weight } => {
+      check_add_fee_recipient(fee_schedule, *account, *weight)?;
+    }
+    AdminAction::RemoveFeeRecipient { account } => {
+      check_remove_fee_recipient(fee_schedule, *account)?;
+    }
+    AdminAction::SetFeeRecipientWeight { account, weight } => {
+      check_fee_recipient_weight(fee_schedule, *account, *weight)?;
+    }
+    AdminAction::SetAdminDelay { admin_delay } => {
+      require!(*admin_delay <= MAX_ADMIN_DELAY, BeanError::InvalidAdminDelay);
+    }
+    AdminAction::SetAdmins { admins, admin_threshold } => {
+      check_admins(admins, *admin_threshold)?;
+    }
+    AdminAction::Reconfigure { new_authority, .. } => {
+      check_reconfigure(fee_schedule, *new_authority)?;
+    }
+    AdminAction::MigrateVault { target_pool, new_vault } => {
+      require!(!is_migrated(global_state), BeanError::PoolMigrated);
+      require!(
+        target_pool.ne(&Pubkey::default()) && new_vault.ne(&Pubkey::default()),
+        BeanError::ZeroAddressDetected
+      );
+    }
+  }
+  Ok(())
+}
+
+/// Credit each fee schedule recipient with its share of `total_fee`,
+/// the lamports stay in the vault until claimed. Returns the unassigned rest
+pub fn accrue_fees(fee_schedule: &mut FeeSchedule, total_fee: u64) -> u64 {
+  let mut remained_fee = total_fee;
+  for recipient in fee_schedule.recipients.iter_mut() {
+    let fee = bps_from_amount(total_fee, recipient.weight);
+    recipient.accrued += fee;
+    remained_fee -= fee;
+  }
+  remained_fee
+}
+
+/// Tickets of participants who revealed their secret
+pub fn revealed_tickets(round: &GiveawayRound) -> u64 {
+  round.participants.iter().filter(|participant| participant.revealed == 1).map(|participant| participant.tickets).sum()
+}
+
+/// Picks the ticket at `seed % total_tickets` among revealed tickets, None when no tickets were revealed
+pub fn pick_giveaway_winner(round: &GiveawayRound) -> Option<Pubkey> {
+  let total_tickets = revealed_tickets(round);
+  if total_tickets == 0 {
+    return None;
+  }
+  let mut seed_bytes = [0u8; 8];
+  seed_bytes.copy_from_slice(&round.seed[..8]);
+  let mut ticket = u64::from_le_bytes(seed_bytes) % total_tickets;
+
+  for participant in round.participants.iter().filter(|participant| participant.revealed == 1) {
+    if ticket < participant.tickets {
+      return Some(participant.user);
+    }
+    ticket -= participant.tickets;
+  }
+  None
+}
+
+/// Restart the epoch counters once the current epoch has elapsed
+pub fn roll_epoch(global_state: &mut GlobalState, cur_timestamp: u64) {
+  let epoch_length = global_state.limits.epoch_length;
+  if epoch_length > 0 && cur_timestamp >= global_state.epoch_start + epoch_length {
+    global_state.epoch_start = cur_timestamp - cur_timestamp % epoch_length;
+    global_state.epoch_deposits = 0;
+    global_state.epoch_withdrawals = 0;
+  }
+}
+
+/// Count a deposit against the TVL cap and the epoch deposit limit
+pub fn record_deposit(global_state: &mut GlobalState, amount: u64, cur_timestamp: u64) -> Result<()> {
+  roll_epoch(global_state, cur_timestamp);
+  let limits = global_state.limits;
+  require!(
+    limits.max_tvl == 0 || global_state.tvl + amount <= limits.max_tvl,
+    BeanError::GlobalTvlReached
+  );
+  require!(
+    limits.epoch_length == 0 || limits.epoch_deposit_limit == 0 ||
+      global_state.epoch_deposits + amount <= limits.epoch_deposit_limit,
+    BeanError::EpochDepositLimitReached
+  );
+  global_state.tvl += amount;
+  global_state.epoch_deposits += amount;
+  Ok(())
+}
+
+/// Count a payout against the epoch withdrawal limit
+pub fn record_withdrawal(global_state: &mut GlobalState, amount: u64, cur_timestamp: u64) -> Result<()> {
+  roll_epoch(global_state, cur_timestamp);
+  let limits = global_state.limits;
+  require!(
+    limits.epoch_length == 0 || limits.epoch_withdrawal_limit == 0 ||
+      global_state.epoch_withdrawals + amount <= limits.epoch_withdrawal_limit,
+    BeanError::EpochWithdrawalLimitReached
+  );
+  global_state.tvl = global_state.tvl.saturating_sub(amount);
+  global_state.epoch_withdrawals += amount;
+  Ok(())
+}
+
+/// Take funds leaving the vault as giveaway shares or claimed fees off the TVL
+pub fn release_tvl(global_state: &mut GlobalState, amount: u64) {
+  global_state.tvl = global_state.tvl.saturating_sub(amount);
+}
+
+/// Earliest time the user can eat again, 0 when they can eat now
+pub fn next_eat_at(global_state: &GlobalState, user_state: &UserState, cur_timestamp: u64) -> u64 {
+  let limits = global_state.limits;
+  let mut next_eat = 0;
+  if limits.eat_cooldown > 0 && user_state.ate_at > 0 {
+    next_eat = user_state.ate_at + limits.eat_cooldown;
+  }
+  if withdrawal_allowance(global_state, user_state, cur_timestamp) == 0 {
+    next_eat = next_eat.max(user_state.withdrawal_window_start + SECONDS_PER_DAY);
+  }
+  if next_eat <= cur_timestamp { 0 } else { next_eat }
+}
+
+/// What the user can still eat in their withdrawal day
+pub fn withdrawal_allowance(global_state: &GlobalState, user_state: &UserState, cur_timestamp: u64) -> u64 {
+  let limit = global_state.limits.user_daily_withdrawal_limit;
+  if limit == 0 {
+    u64::MAX
+  } else if cur_timestamp >= user_state.withdrawal_window_start + SECONDS_PER_DAY {
+    limit
+  } else {
+    limit.saturating_sub(user_state.window_withdrawals)
+  }
+}
+
+/// Check the eat cooldown and count `amount` against the user's daily withdrawal limit
+pub fn record_user_withdrawal(
+  global_state: &GlobalState,
+  user_state: &mut UserState,
+  amount: u64,
+  cur_timestamp: u64,
+) -> Result<()> {
+  let eat_cooldown = global_state.limits.eat_cooldown;
+  require!(
+    eat_cooldown == 0 || user_state.ate_at == 0 || cur_timestamp >= user_state.ate_at + eat_cooldown,
+    BeanError::EatCooldownActive
+  );
+  count_user_withdrawal(global_state, user_state, amount, cur_timestamp)
+}
+
+/// Count `amount` against the user's daily withdrawal limit
+pub fn count_user_withdrawal(
+  global_state: &GlobalState,
+  user_state: &mut UserState,
+  amount: u64,
+  cur_timestamp: u64,
+) -> Result<()> {
+  require!(
+    amount <= withdrawal_allowance(global_state, user_state, cur_timestamp),
+    BeanError::UserWithdrawalLimitReached
+  );
+  if cur_timestamp >= user_state.withdrawal_window_start + SECONDS_PER_DAY {
+    user_state.withdrawal_window_start = cur_timestamp;
+    user_state.window_withdrawals = 0;
+  }
+  user_state.window_withdrawals += amount;
+  Ok(())
+}
+
+/// Deposits are closed before launch, and open only to whitelisted users until the whitelist ends
+pub fn check_launch(global_state: &GlobalState, user_key: Pubkey, proof: &[[u8; 32]], cur_timestamp: u64) -> Result<()> {
+  require!(cur_timestamp >= global_state.launch_time, BeanError::NotLaunched);
+  if cur_timestamp < global_state.whitelist_end {
+    require!(
+      verify_merkle_proof(proof, global_state.whitelist_root, hash(user_key.as_ref()).to_bytes()),
+      BeanError::NotWhitelisted
+    );
+  }
+  Ok(())
+}
+
+/// Fold `proof` into `leaf` hashing sorted pairs, true when it ends at `root`
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+  let mut computed = leaf;
+  for node in proof.iter() {
+    computed = if computed <= *node {
+      hashv(&[&computed, node]).to_bytes()
+    } else {
+      hashv(&[node, &computed]).to_bytes()
+    };
+  }
+  computed == root
+}
+
+/// The wallet controlling the position: the user, or the holder of its position NFT
+pub fn position_owner(user_state: &UserState, position_token: &AccountInfo) -> Result<Pubkey> {
+  if user_state.position_mint.eq(&Pubkey::default()) {
+    return Ok(user_state.user);
+  }
+  let token_account = load_token_account(position_token)?;
+  require!(
+    token_account.mint.eq(&user_state.position_mint) && token_account.amount == 1,
+    BeanError::NotPositionHolder
+  );
+  Ok(token_account.owner)
+}
+
+pub fn is_owner_or_delegate(user_state: &UserState, owner: Pubkey, signer: Pubkey) -> bool {
+  owner.eq(&signer) ||
+    (user_state.delegate.ne(&Pubkey::default()) && user_state.delegate.eq(&signer))
+}
+
+pub fn can_eat(user_state: &UserState, owner: Pubkey, signer: Pubkey) -> bool {
+  owner.eq(&signer) ||
+    (user_state.delegate_can_eat == 1 && is_owner_or_delegate(user_state, owner, signer))
+}
+
+pub fn recipient_allowed(user_state: &UserState, owner: Pubkey, signer: Pubkey, recipient: Pubkey) -> bool {
+  if recipient.eq(&owner) || user_state.recipient_whitelist.contains(&recipient) {
+    return true;
+  }
+  // delegates can only route proceeds to the owner or a whitelisted recipient
+  signer.eq(&owner) && user_state.recipient_whitelist.is_empty()
+}
+
+pub fn payer_allowed(global_state: &GlobalState, payer: Pubkey, user_key: Pubkey) -> bool {
+  global_state.restrict_user_init == 0 || payer.eq(&user_key) || global_state.relayers.contains(&payer)
 }
 
 pub fn ref_exists(ref_user_state: &UserState, user_key: Pubkey) -> bool {
@@ -124,3 +730,209 @@ pub fn ref_exists(ref_user_state: &UserState, user_key: Pubkey) -> bool {
   res.is_some()
 }
 
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn curve(curve: u8, cyclic: u8, period: u8, start_tax: u64, min_tax: u64, decay: u64) -> TaxSchedule {
+    TaxSchedule { curve, cyclic, period, start_tax, min_tax, decay, ..TaxSchedule::default() }
+  }
+
+  #[test]
+  fn table_tax_repeats_when_cyclic() {
+    let schedule = default_tax_schedule();
+    assert_eq!(tax_for_days(&schedule, 0), 9000);
+    assert_eq!(tax_for_days(&schedule, 9), 0);
+    assert_eq!(tax_for_days(&schedule, 10), 9000);
+    assert_eq!(tax_for_days(&schedule, 13), 6000);
+  }
+
+  #[test]
+  fn table_tax_stays_on_last_day_when_not_cyclic() {
+    let schedule = TaxSchedule { cyclic: 0, ..default_tax_schedule() };
+    assert_eq!(tax_for_days(&schedule, 3), 6000);
+    assert_eq!(tax_for_days(&schedule, 9), 0);
+    assert_eq!(tax_for_days(&schedule, 25), 0);
+  }
+
+  #[test]
+  fn linear_tax_decays_to_floor() {
+    let schedule = curve(TAX_CURVE_LINEAR, 0, 30, 5000, 500, 1000);
+    assert_eq!(tax_for_days(&schedule, 0), 5000);
+    assert_eq!(tax_for_days(&schedule, 2), 3000);
+    assert_eq!(tax_for_days(&schedule, 10), 500);
+
+    let cyclic = curve(TAX_CURVE_LINEAR, 1, 5, 5000, 500, 1000);
+    assert_eq!(tax_for_days(&cyclic, 7), 3000);
+  }
+
+  #[test]
+  fn exponential_tax_keeps_floor() {
+    let schedule = curve(TAX_CURVE_EXPONENTIAL, 0, 30, 8000, 1000, 5000);
+    assert_eq!(tax_for_days(&schedule, 1), 4000);
+    assert_eq!(tax_for_days(&schedule, 3), 1000);
+    assert_eq!(tax_for_days(&schedule, 4), 1000);
+    assert_eq!(tax_for_days(&schedule, 365), 1000);
+
+    // a decay step below the floor lands on the floor
+    let floored = curve(TAX_CURVE_EXPONENTIAL, 0, 30, 8000, 3000, 5000);
+    assert_eq!(tax_for_days(&floored, 2), 3000);
+
+    let cyclic = curve(TAX_CURVE_EXPONENTIAL, 1, 3, 8000, 1000, 5000);
+    assert_eq!(tax_for_days(&cyclic, 3), 8000);
+    assert_eq!(tax_for_days(&cyclic, 4), 4000);
+  }
+
+  #[test]
+  fn invalid_tax_schedules_are_rejected() {
+    assert!(valid_tax_schedule(&default_tax_schedule()));
+    assert!(!valid_tax_schedule(&curve(TAX_CURVE_LINEAR, 0, 0, 5000, 500, 1000)));
+    assert!(!valid_tax_schedule(&curve(TAX_CURVE_LINEAR, 0, 30, 500, 5000, 1000)));
+    assert!(!valid_tax_schedule(&curve(TAX_CURVE_EXPONENTIAL, 0, 30, 8000, 1000, BPS_DENOMINATOR)));
+  }
+
+  #[test]
+  fn eat_reaching_max_payout_pays_the_untaxed_rest() {
+    // 5% fee, 50% tax
+    assert_eq!(split_eat(1000, 5000, 5000), (50, 237, 475, 500));
+    assert_eq!(split_eat(1000, 5000, 800), (50, 237, 800, 800));
+    assert_eq!(split_eat(1000, 0, 1000), (50, 0, 1000, 1000));
+  }
+
+  fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    if a <= b { hashv(&[&a, &b]).to_bytes() } else { hashv(&[&b, &a]).to_bytes() }
+  }
+
+  #[test]
+  fn merkle_proofs_verify_whitelisted_users() {
+    let users: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let leaves: Vec<[u8; 32]> = users.iter().map(|user| hash(user.as_ref()).to_bytes()).collect();
+    let left = hash_pair(leaves[0], leaves[1]);
+    let root = hash_pair(left, leaves[2]);
+
+    assert!(verify_merkle_proof(&[leaves[1], leaves[2]], root, leaves[0]));
+    assert!(verify_merkle_proof(&[leaves[0], leaves[2]], root, leaves[1]));
+    assert!(verify_merkle_proof(&[left], root, leaves[2]));
+    // a single whitelisted user is the root itself
+    assert!(verify_merkle_proof(&[], leaves[0], leaves[0]));
+
+    let outsider = hash(Pubkey::new_unique().as_ref()).to_bytes();
+    assert!(!verify_merkle_proof(&[leaves[1], leaves[2]], root, outsider));
+    assert!(!verify_merkle_proof(&[leaves[2]], root, leaves[0]));
+    assert!(!verify_merkle_proof(&[leaves[1], leaves[2]], left, leaves[0]));
+  }
+
+  #[test]
+  fn admin_sets_are_validated() {
+    let admins: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    assert!(check_admins(&[], 0).is_ok());
+    assert!(check_admins(&admins, 2).is_ok());
+    assert!(check_admins(&admins, 3).is_ok());
+    assert!(check_admins(&admins, 0).is_err());
+    assert!(check_admins(&admins, 4).is_err());
+    assert!(check_admins(&[], 1).is_err());
+    assert!(check_admins(&[admins[0], admins[0]], 1).is_err());
+    assert!(check_admins(&[admins[0], Pubkey::default()], 1).is_err());
+    let too_many: Vec<Pubkey> = (0..MAX_ADMINS + 1).map(|_| Pubkey::new_unique()).collect();
+    assert!(check_admins(&too_many, 1).is_err());
+  }
+
+  #[test]
+  fn only_current_admins_count_as_approvals() {
+    let authority = Pubkey::new_unique();
+    let admins: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let mut global_state = GlobalState { authority, ..GlobalState::default() };
+    assert!(is_admin(&global_state, authority));
+    assert!(!is_admin(&global_state, admins[0]));
+
+    global_state.admin_threshold = 2;
+    global_state.admins[..3].copy_from_slice(&admins);
+    assert!(!is_admin(&global_state, authority));
+    assert!(!is_admin(&global_state, Pubkey::default()));
+
+    let removed = Pubkey::new_unique();
+    let queued_action = QueuedAction {
+      approvals: vec![admins[0], removed, admins[2]],
+      ..QueuedAction::default()
+    };
+    assert_eq!(approval_count(&global_state, &queued_action), 2);
+
+    global_state.admins[2] = Pubkey::default();
+    assert_eq!(approval_count(&global_state, &queued_action), 1);
+  }
+
+  fn lock(beans: u64, boost: u64, unlock_at: u64) -> LockPosition {
+    LockPosition { beans, boost, unlock_at, ..LockPosition::default() }
+  }
+
+  #[test]
+  fn lock_reward_is_boosted_until_unlock() {
+    let beans = 1_000_000;
+    // 3% base reward, 2% boost, over two days
+    let period = 2 * SECONDS_PER_DAY;
+    assert_eq!(calc_lock_reward(&lock(beans, 200, 0), 0, period, 300), 60_000);
+    assert_eq!(calc_lock_reward(&lock(beans, 200, SECONDS_PER_DAY), 0, period, 300), 80_000);
+    assert_eq!(calc_lock_reward(&lock(beans, 200, 10 * SECONDS_PER_DAY), 0, period, 300), 100_000);
+    // unlocking halfway through a period that started later
+    assert_eq!(calc_lock_reward(&lock(beans, 200, 3 * SECONDS_PER_DAY), 2 * SECONDS_PER_DAY, period, 300), 80_000);
+  }
+
+  #[test]
+  fn settle_holds_locked_rewards_and_releases_unlocked_ones() {
+    let baked_at = 1_000_000;
+    let now = baked_at + SECONDS_PER_DAY;
+    let user_state = UserState { baked_at, ..UserState::default() };
+    let mut unlocked = lock(1_000_000, 200, baked_at);
+    unlocked.accrued_beans = 5_000;
+    let mut positions = vec![lock(1_000_000, 200, now + SECONDS_PER_DAY), unlocked];
+
+    // 50000 boosted for the locked position, 30000 at the base rate and 5000 held for the unlocked one
+    assert_eq!(settle_locks(&user_state, &mut positions, now), 35_000);
+    assert_eq!(positions[0].accrued_beans, 50_000);
+    assert_eq!(positions[1].accrued_beans, 0);
+  }
+
+  #[test]
+  fn settle_holds_no_more_than_the_capped_rewards() {
+    let cap = sol_to_beans(MAX_DAILY_REWARDS_IN_SOL);
+    let baked_at = 1_000_000;
+    let now = baked_at + SECONDS_PER_DAY;
+    let user_state = UserState { baked_at, ..UserState::default() };
+    let mut positions = vec![lock(cap * 100, 200, now + SECONDS_PER_DAY)];
+
+    assert_eq!(settle_locks(&user_state, &mut positions, now), 0);
+    assert_eq!(positions[0].accrued_beans, cap);
+  }
+
+  fn round_with_seed(participants: Vec<GiveawayTicket>, pick: u64) -> GiveawayRound {
+    let mut seed = [0u8; 32];
+    seed[..8].copy_from_slice(&pick.to_le_bytes());
+    let total_tickets = participants.iter().map(|participant| participant.tickets).sum();
+    GiveawayRound { seed, total_tickets, participants, ..GiveawayRound::default() }
+  }
+
+  fn ticket(user: Pubkey, tickets: u64, revealed: u8) -> GiveawayTicket {
+    GiveawayTicket { user, tickets, revealed, ..GiveawayTicket::default() }
+  }
+
+  #[test]
+  fn winner_is_drawn_among_revealed_tickets() {
+    let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let participants = vec![ticket(a, 2, 1), ticket(b, 3, 0), ticket(c, 1, 1)];
+
+    assert_eq!(revealed_tickets(&round_with_seed(participants.clone(), 0)), 3);
+    assert_eq!(pick_giveaway_winner(&round_with_seed(participants.clone(), 0)), Some(a));
+    assert_eq!(pick_giveaway_winner(&round_with_seed(participants.clone(), 1)), Some(a));
+    assert_eq!(pick_giveaway_winner(&round_with_seed(participants.clone(), 2)), Some(c));
+    assert_eq!(pick_giveaway_winner(&round_with_seed(participants.clone(), 3)), Some(a));
+    assert_eq!(pick_giveaway_winner(&round_with_seed(participants, 5)), Some(c));
+  }
+
+  #[test]
+  fn no_winner_without_revealed_tickets() {
+    assert_eq!(pick_giveaway_winner(&round_with_seed(vec![], 7)), None);
+    let unrevealed = vec![ticket(Pubkey::new_unique(), 4, 0)];
+    assert_eq!(pick_giveaway_winner(&round_with_seed(unrevealed, 7)), None);
+  }
+}
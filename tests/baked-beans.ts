@@ -2,6 +2,7 @@ import * as anchor from "@project-serum/anchor";
 import { Program } from "@project-serum/anchor";
 import { BakedBeans } from "../target/types/baked_beans";
 import { IDL } from "../target/types/baked_beans";
+import { assert } from "chai";
 import {
   LAMPORTS_PER_SOL,
   PublicKey,
@@ -17,6 +18,11 @@ import {
 const GLOBAL_STATE_SEED = "GLOBAL_STATE_SEED";
 const VAULT_SEED = "VAULT_SEED";
 const USER_STATE_SEED = "USER_STATE_SEED";
+const FEE_SCHEDULE_SEED = "FEE_SCHEDULE_SEED";
+const GIVEAWAY_SEED = "GIVEAWAY_SEED";
+// pool created by `initialize`
+const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+const ROOT_POOL_ID = new anchor.BN(0).toArrayLike(Buffer, "le", 8);
 
 const delay = (delayInms) => {
   return new Promise((resolve) => {
@@ -44,26 +50,42 @@ describe("baked-beans", () => {
     await airdropSol(provider, admin.publicKey, 10000000000);
     console.log(program.programId.toBase58());
     const [globalStateKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(GLOBAL_STATE_SEED)],
+      [Buffer.from(GLOBAL_STATE_SEED), ROOT_POOL_ID],
       program.programId
     );
     console.log(globalStateKey.toBase58());
     const [vaultKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(VAULT_SEED)],
+      [Buffer.from(VAULT_SEED), globalStateKey.toBuffer()],
       program.programId
     );
     console.log(vaultKey.toBase58());
+    const [feeScheduleKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from(FEE_SCHEDULE_SEED), globalStateKey.toBuffer()],
+      program.programId
+    );
+    const [giveawayPoolKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from(GIVEAWAY_SEED), globalStateKey.toBuffer()],
+      program.programId
+    );
+    const [programDataKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_ID
+    );
+    // only the upgrade authority, the deploying wallet, can initialize
     const tx = new Transaction().add(
       await program.methods
         .initialize(admin.publicKey)
         .accounts({
-          authority: admin.publicKey,
+          authority: provider.wallet.publicKey,
+          program: program.programId,
+          programData: programDataKey,
           globalState: globalStateKey,
+          feeSchedule: feeScheduleKey,
           devAccount: admin.publicKey,
           marketingAccount: admin.publicKey,
           ceoAccount: admin.publicKey,
-          giveawayAccount: admin.publicKey,
           vault: vaultKey,
+          giveawayPool: giveawayPoolKey,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY
         })
@@ -71,25 +93,25 @@ describe("baked-beans", () => {
     );
     //let simulRes = await provider.simulate(tx, [admin]);
     //console.log('simulRes =', simulRes);
-    let txHash = await sendAndConfirmTransaction(provider.connection, tx, [admin]);
+    let txHash = await provider.send(tx);
     console.log("Your transaction signature", txHash);
   });
 
   it("buy and bake beans", async () => {
     const [globalStateKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(GLOBAL_STATE_SEED)],
+      [Buffer.from(GLOBAL_STATE_SEED), ROOT_POOL_ID],
       program.programId
     );
     const [vaultKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(VAULT_SEED)],
+      [Buffer.from(VAULT_SEED), globalStateKey.toBuffer()],
       program.programId
     );
     const [userStateKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(USER_STATE_SEED), user.publicKey.toBuffer()],
+      [Buffer.from(USER_STATE_SEED), globalStateKey.toBuffer(), user.publicKey.toBuffer()],
       program.programId
     );
     const [adminUserStateKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(USER_STATE_SEED), admin.publicKey.toBuffer()],
+      [Buffer.from(USER_STATE_SEED), globalStateKey.toBuffer(), admin.publicKey.toBuffer()],
       program.programId
     );
     let userData = await program.account.userState.fetchNullable(userStateKey);
@@ -100,6 +122,7 @@ describe("baked-beans", () => {
           program,
           user,
           user.publicKey,
+          globalStateKey,
           userStateKey
         )
       )
@@ -107,7 +130,7 @@ describe("baked-beans", () => {
     let referrer = admin.publicKey;
     
     const [refUserStateKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(USER_STATE_SEED), referrer.toBuffer()],
+      [Buffer.from(USER_STATE_SEED), globalStateKey.toBuffer(), referrer.toBuffer()],
       program.programId
     );
     
@@ -125,24 +148,26 @@ describe("baked-beans", () => {
             program,
             user,
             admin.publicKey,
+            globalStateKey,
             adminUserStateKey
           )
         )
       }
     }
 
-    let globalData = await program.account.globalState.fetch(globalStateKey);
+    const [feeScheduleKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from(FEE_SCHEDULE_SEED), globalStateKey.toBuffer()],
+      program.programId
+    );
     const tx = new Transaction();
     if (instructions.length > 0) tx.add(...instructions);
     tx.add(
       await program.methods
-        .buyBeans(referrer, new anchor.BN(5).mul(new anchor.BN(LAMPORTS_PER_SOL)))
+        .buyBeans(referrer, new anchor.BN(5).mul(new anchor.BN(LAMPORTS_PER_SOL)), [])
         .accounts({
           user: user.publicKey,
           globalState: globalStateKey,
-          devAccount: globalData.devAccount,
-          ceoAccount: globalData.ceoAccount,
-          marketingAccount: globalData.marketingAccount,
+          feeSchedule: feeScheduleKey,
           vault: vaultKey,
           userState: userStateKey,
           refUserState: referrerUserStateKey,
@@ -159,6 +184,8 @@ describe("baked-beans", () => {
           globalState: globalStateKey,
           vault: vaultKey,
           userState: userStateKey,
+          // the position has no NFT, any account will do
+          positionToken: user.publicKey,
           systemProgram: SystemProgram.programId,
           rent: SYSVAR_RENT_PUBKEY
         })
@@ -179,32 +206,38 @@ describe("baked-beans", () => {
   it("eat beans", async () => {
     await delay(2000);
     const [globalStateKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(GLOBAL_STATE_SEED)],
+      [Buffer.from(GLOBAL_STATE_SEED), ROOT_POOL_ID],
       program.programId
     );
     const [vaultKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(VAULT_SEED)],
+      [Buffer.from(VAULT_SEED), globalStateKey.toBuffer()],
       program.programId
     );
     const [userStateKey] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from(USER_STATE_SEED), user.publicKey.toBuffer()],
+      [Buffer.from(USER_STATE_SEED), globalStateKey.toBuffer(), user.publicKey.toBuffer()],
+      program.programId
+    );
+    const [feeScheduleKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from(FEE_SCHEDULE_SEED), globalStateKey.toBuffer()],
+      program.programId
+    );
+    const [giveawayPoolKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from(GIVEAWAY_SEED), globalStateKey.toBuffer()],
       program.programId
     );
-    let globalData = await program.account.globalState.fetch(globalStateKey);
     const tx = new Transaction().add(
       await program.methods
         .eatBeans()
         .accounts({
           user: user.publicKey,
+          recipient: user.publicKey,
           globalState: globalStateKey,
           vault: vaultKey,
-          
-          devAccount: admin.publicKey,
-          marketingAccount: admin.publicKey,
-          ceoAccount: admin.publicKey,
-          giveawayAccount: admin.publicKey,
+          feeSchedule: feeScheduleKey,
+          giveawayPool: giveawayPoolKey,
 
           userState: userStateKey,
+          positionToken: user.publicKey,
           systemProgram: SystemProgram.programId
         })
         .instruction()
@@ -218,6 +251,48 @@ describe("baked-beans", () => {
     let solBal = await provider.connection.getBalance(user.publicKey);
     console.log(solBal);
   })
+
+  it("claim fees", async () => {
+    const [globalStateKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from(GLOBAL_STATE_SEED), ROOT_POOL_ID],
+      program.programId
+    );
+    const [vaultKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from(VAULT_SEED), globalStateKey.toBuffer()],
+      program.programId
+    );
+    const [feeScheduleKey] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from(FEE_SCHEDULE_SEED), globalStateKey.toBuffer()],
+      program.programId
+    );
+    // admin is the dev, marketing and ceo account, a claim pays all three shares
+    const accruedBefore = (await program.account.feeSchedule.fetch(feeScheduleKey)).recipients
+      .filter((r) => r.account.equals(admin.publicKey))
+      .reduce((sum, r) => sum + r.accrued.toNumber(), 0);
+    assert.isAbove(accruedBefore, 0);
+    const balanceBefore = await provider.connection.getBalance(admin.publicKey);
+
+    const tx = new Transaction().add(
+      await program.methods
+        .claimFees()
+        .accounts({
+          globalState: globalStateKey,
+          feeSchedule: feeScheduleKey,
+          vault: vaultKey,
+          recipient: admin.publicKey,
+          systemProgram: SystemProgram.programId
+        })
+        .instruction()
+    );
+    let txHash = await sendAndConfirmTransaction(provider.connection, tx, [user]);
+    console.log("Your transaction signature", txHash);
+
+    let feeSchedule = await program.account.feeSchedule.fetch(feeScheduleKey);
+    feeSchedule.recipients.forEach((r) => assert.equal(r.accrued.toNumber(), 0));
+    // the user paid the transaction, the admin only receives
+    const balanceAfter = await provider.connection.getBalance(admin.publicKey);
+    assert.equal(balanceAfter - balanceBefore, accruedBefore);
+  })
 });
 
 export const airdropSol = async (
@@ -234,12 +309,14 @@ export const createUserStateInstruction = async (
   program: any,
   payer: Keypair,
   userKey: PublicKey,
+  globalStateKey: PublicKey,
   userStateKey: PublicKey
 ) => {
   return await program.methods
     .initUserState(userKey)
     .accounts({
       payer: payer.publicKey,
+      globalState: globalStateKey,
       userState: userStateKey,
       systemProgram: SystemProgram.programId,
       rent: SYSVAR_RENT_PUBKEY,